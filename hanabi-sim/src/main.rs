@@ -0,0 +1,644 @@
+use std::any::Any;
+use std::ops::Range;
+use std::panic;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::thread;
+
+use itertools::{Either, Itertools};
+use rand::rngs::StdRng;
+use serde::Serialize;
+use shared::bot::{play_game, Bot, RandomBot, SimpleBot};
+use shared::conventions::ConventionBot;
+use shared::model::{
+    GameConfig, GameOutcome, GameState, GameStatus, PlayerAction, RuleValidation, Variant,
+};
+use shared::search::{SearchBot, SearchBudget};
+use shared::solver::solve_seed;
+
+const USAGE: &str = "\
+Plays games between bots and reports how they went
+
+Usage: hanabi-sim [options]
+
+Options:
+  --players <count>     players at the table, 2 to 5 [default: 3]
+  --variant <variant>   standard, rainbow, black, up-or-down or
+                        throw-it-in-a-hole [default: standard]
+  --rules <rules>       lenient or strict [default: lenient]
+  --seeds <start>..<end>
+                        deck seeds to play, one game each [default: 0..1000]
+  --bots <bot>[,<bot>]  the bot of every seat, or one bot for all of them
+                        random, simple, convention or search [default: convention]
+  --samples <count>     deals the search bot simulates per move [default: 20]
+  --threads <count>     games played at once [default: every core]
+  --solve               tells which seeds can end perfectly with every card
+                        visible, instead of playing them
+  --nodes <count>       positions the solver searches per seed before giving
+                        up [default: 100000]
+  --json                reports as JSON instead of text
+  --help                shows this message
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BotKind {
+    Random,
+    Simple,
+    Convention,
+    Search,
+}
+
+impl FromStr for BotKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "random" => Ok(BotKind::Random),
+            "simple" => Ok(BotKind::Simple),
+            "convention" => Ok(BotKind::Convention),
+            "search" => Ok(BotKind::Search),
+            _ => Err(format!("unknown bot {name}")),
+        }
+    }
+}
+
+impl BotKind {
+    fn name(&self) -> &'static str {
+        match self {
+            BotKind::Random => "random",
+            BotKind::Simple => "simple",
+            BotKind::Convention => "convention",
+            BotKind::Search => "search",
+        }
+    }
+
+    /// Bots with dice of their own throw them differently for every game and seat
+    fn create(&self, seed: u64, seat: usize, samples: usize) -> Box<dyn Bot> {
+        let seed = seed.wrapping_mul(8).wrapping_add(seat as u64);
+        match self {
+            BotKind::Random => Box::new(RandomBot::new(seed)),
+            BotKind::Simple => Box::new(SimpleBot::new()),
+            BotKind::Convention => Box::new(ConventionBot::new()),
+            BotKind::Search => Box::new(SearchBot::new(seed, SearchBudget::samples(samples))),
+        }
+    }
+}
+
+fn parse_variant(name: &str) -> Result<Variant, String> {
+    match name {
+        "standard" => Ok(Variant::Standard),
+        "rainbow" => Ok(Variant::Rainbow),
+        "black" => Ok(Variant::Black),
+        "up-or-down" => Ok(Variant::UpOrDown),
+        "throw-it-in-a-hole" => Ok(Variant::ThrowItInAHole),
+        _ => Err(format!("unknown variant {name}")),
+    }
+}
+
+fn parse_rules(name: &str) -> Result<RuleValidation, String> {
+    match name {
+        "lenient" => Ok(RuleValidation::Lenient),
+        "strict" => Ok(RuleValidation::Strict),
+        _ => Err(format!("unknown rules {name}")),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Options {
+    players: usize,
+    variant: Variant,
+    rules: RuleValidation,
+    seeds: Range<u64>,
+    bots: Vec<BotKind>,
+    samples: usize,
+    threads: usize,
+    solve: bool,
+    nodes: usize,
+    json: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            players: 3,
+            variant: Variant::default(),
+            rules: RuleValidation::default(),
+            seeds: 0..1000,
+            bots: vec![BotKind::Convention],
+            samples: 20,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            solve: false,
+            nodes: 100_000,
+            json: false,
+        }
+    }
+}
+
+impl Options {
+    /// The game of a seed
+    fn config(&self, seed: u64) -> GameConfig {
+        GameConfig::new(self.players, seed)
+            .with_variant(self.variant)
+            .with_rules(self.rules)
+    }
+}
+
+/// The options, nothing when asked for help, or the message to leave with
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options::default();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        let number = |value: String| {
+            value
+                .parse::<usize>()
+                .map_err(|_| format!("{arg} needs a number, not {value}"))
+        };
+
+        match arg.as_str() {
+            "--players" => options.players = number(value()?)?,
+            "--variant" => options.variant = parse_variant(&value()?)?,
+            "--rules" => options.rules = parse_rules(&value()?)?,
+            "--seeds" => {
+                let value = value()?;
+                let (start, end) = value
+                    .split_once("..")
+                    .and_then(|(start, end)| Some((start.parse().ok()?, end.parse().ok()?)))
+                    .ok_or(format!("--seeds needs a range like 0..1000, not {value}"))?;
+                options.seeds = start..end;
+            }
+            "--bots" => {
+                options.bots = value()?
+                    .split(',')
+                    .map(BotKind::from_str)
+                    .collect::<Result<_, _>>()?
+            }
+            "--samples" => options.samples = number(value()?)?,
+            "--threads" => options.threads = number(value()?)?.max(1),
+            "--solve" => options.solve = true,
+            "--nodes" => options.nodes = number(value()?)?,
+            "--json" => options.json = true,
+            "--help" => return Ok(None),
+            _ => return Err(format!("unknown option {arg}\n\n{USAGE}")),
+        }
+    }
+
+    if !(2..=5).contains(&options.players) {
+        return Err(format!("{} players can't play", options.players));
+    }
+    match options.bots.len() {
+        1 => options.bots = vec![options.bots[0]; options.players],
+        count if count != options.players => {
+            return Err(format!("{count} bots for {} players", options.players));
+        }
+        _ => {}
+    }
+    Ok(Some(options))
+}
+
+#[derive(Debug, Clone)]
+struct GameResult {
+    score: usize,
+    won: bool,
+    bombed_out: bool,
+    turns: usize,
+}
+
+/// The outcome of a finished game, if it agrees with the cards on the board
+fn check_outcome(game_state: &GameState) -> Result<GameOutcome, String> {
+    let GameStatus::Finished(outcome) = game_state.status else {
+        return Err(format!("the game stopped while {:?}", game_state.status));
+    };
+    let score = game_state.played_cards.score();
+    let max_score = game_state.config.variant.max_score();
+
+    match outcome {
+        GameOutcome::Win if score == max_score => Ok(outcome),
+        GameOutcome::Fail { score: reported } if reported == score && score < max_score => {
+            Ok(outcome)
+        }
+        _ => Err(format!("{outcome:?} with {score} cards played")),
+    }
+}
+
+fn run_game(options: &Options, seed: u64) -> Result<GameResult, String> {
+    let mut bots = options
+        .bots
+        .iter()
+        .enumerate()
+        .map(|(seat, bot)| bot.create(seed, seat, options.samples))
+        .collect_vec();
+    let game_log = play_game::<StdRng>(options.config(seed), &mut bots)
+        .map_err(|error| format!("{error:?}"))?;
+
+    let game_state = game_log.current_game_state();
+    let outcome = check_outcome(&game_state)?;
+    Ok(GameResult {
+        score: game_state.played_cards.score(),
+        won: outcome == GameOutcome::Win,
+        bombed_out: game_state.remaining_bomb_count == 0,
+        turns: game_log
+            .log
+            .iter()
+            .filter(|event| !matches!(event.event_action, PlayerAction::MoveSlot(_, _, _)))
+            .count(),
+    })
+}
+
+/// What a panic was raised with, when it's text
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "a panic without a message".to_string(),
+        },
+    }
+}
+
+/// Runs every seed, spread over the threads, in the order of the seeds. A seed
+/// that panics fails on its own, the others still run.
+fn run_seeds<T: Send>(
+    options: &Options,
+    run: fn(&Options, u64) -> Result<T, String>,
+) -> Vec<(u64, Result<T, String>)> {
+    let seeds = options.seeds.clone().collect_vec();
+    let chunk_size = seeds.len().div_ceil(options.threads).max(1);
+
+    thread::scope(|scope| {
+        let handles = seeds
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|&seed| {
+                            let result = panic::catch_unwind(|| run(options, seed)).unwrap_or_else(
+                                |payload| Err(format!("panicked: {}", panic_message(payload))),
+                            );
+                            (seed, result)
+                        })
+                        .collect_vec()
+                })
+            })
+            .collect_vec();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("games don't panic past catch_unwind"))
+            .collect()
+    })
+}
+
+/// Whether a seed can end with a perfect score, for players seeing every card
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum SeedLabel {
+    Perfect,
+    Impossible,
+    /// The solver gave up before telling
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SolvedSeed {
+    seed: u64,
+    best_score: usize,
+    /// Proven best when the solver didn't give up
+    upper_bound: usize,
+    label: SeedLabel,
+}
+
+fn solve_game(options: &Options, seed: u64) -> Result<SolvedSeed, String> {
+    let config = options.config(seed);
+    let max_score = config.variant.max_score();
+    let solution =
+        solve_seed::<StdRng>(&config, options.nodes).map_err(|error| format!("{error:?}"))?;
+
+    let label = if solution.best_score == max_score {
+        SeedLabel::Perfect
+    } else if solution.upper_bound < max_score {
+        SeedLabel::Impossible
+    } else {
+        SeedLabel::Unknown
+    };
+    Ok(SolvedSeed {
+        seed,
+        best_score: solution.best_score,
+        upper_bound: solution.upper_bound,
+        label,
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Failure {
+    seed: u64,
+    error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Report {
+    players: usize,
+    variant: Variant,
+    rules: RuleValidation,
+    seeds: Range<u64>,
+    bots: Vec<&'static str>,
+    games: usize,
+    average_score: f64,
+    /// Games ending on each score, from zero up to a perfect score
+    score_distribution: Vec<usize>,
+    win_rate: f64,
+    bomb_out_rate: f64,
+    average_turns: f64,
+    /// Games the engine couldn't finish with a valid outcome
+    failures: Vec<Failure>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SolveReport {
+    players: usize,
+    variant: Variant,
+    rules: RuleValidation,
+    seeds: Range<u64>,
+    perfect: usize,
+    impossible: usize,
+    unknown: usize,
+    solved: Vec<SolvedSeed>,
+    failures: Vec<Failure>,
+}
+
+impl SolveReport {
+    fn new(options: &Options, seeds: Vec<(u64, Result<SolvedSeed, String>)>) -> Self {
+        let (solved, failures): (Vec<_>, Vec<_>) =
+            seeds
+                .into_iter()
+                .partition_map(|(seed, result)| match result {
+                    Ok(solved) => Either::Left(solved),
+                    Err(error) => Either::Right(Failure { seed, error }),
+                });
+        let count = |label| solved.iter().filter(|seed| seed.label == label).count();
+
+        SolveReport {
+            players: options.players,
+            variant: options.variant,
+            rules: options.rules,
+            seeds: options.seeds.clone(),
+            perfect: count(SeedLabel::Perfect),
+            impossible: count(SeedLabel::Impossible),
+            unknown: count(SeedLabel::Unknown),
+            solved,
+            failures,
+        }
+    }
+
+    /// The counts, then every seed that isn't perfect
+    fn to_text(&self) -> String {
+        let mut text = format!(
+            "{} seeds of {} players, {} with {:?} rules, seeds {}..{}\n\
+             perfect     {}\n\
+             impossible  {}\n\
+             unknown     {}\n",
+            self.solved.len(),
+            self.players,
+            self.variant.name(),
+            self.rules,
+            self.seeds.start,
+            self.seeds.end,
+            self.perfect,
+            self.impossible,
+            self.unknown,
+        );
+
+        for seed in &self.solved {
+            match seed.label {
+                SeedLabel::Perfect => {}
+                SeedLabel::Impossible => {
+                    text += &format!(
+                        "seed {} is impossible, {} at best\n",
+                        seed.seed, seed.best_score
+                    )
+                }
+                SeedLabel::Unknown => {
+                    text += &format!(
+                        "seed {} is unknown, between {} and {}\n",
+                        seed.seed, seed.best_score, seed.upper_bound
+                    )
+                }
+            }
+        }
+        for failure in &self.failures {
+            text += &format!("seed {} failed: {}\n", failure.seed, failure.error);
+        }
+        text
+    }
+}
+
+impl Report {
+    fn new(
+        options: &Options,
+        max_score: usize,
+        games: Vec<(u64, Result<GameResult, String>)>,
+    ) -> Self {
+        let (results, failures): (Vec<_>, Vec<_>) =
+            games
+                .into_iter()
+                .partition_map(|(seed, result)| match result {
+                    Ok(result) => Either::Left(result),
+                    Err(error) => Either::Right(Failure { seed, error }),
+                });
+
+        let count = results.len().max(1) as f64;
+        let share = |predicate: fn(&GameResult) -> bool| {
+            results.iter().filter(|result| predicate(result)).count() as f64 / count
+        };
+        let mut score_distribution = vec![0; max_score + 1];
+        for result in &results {
+            score_distribution[result.score.min(max_score)] += 1;
+        }
+
+        Report {
+            players: options.players,
+            variant: options.variant,
+            rules: options.rules,
+            seeds: options.seeds.clone(),
+            bots: options.bots.iter().map(BotKind::name).collect(),
+            games: results.len(),
+            average_score: results.iter().map(|result| result.score).sum::<usize>() as f64 / count,
+            score_distribution,
+            win_rate: share(|result| result.won),
+            bomb_out_rate: share(|result| result.bombed_out),
+            average_turns: results.iter().map(|result| result.turns).sum::<usize>() as f64 / count,
+            failures,
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!(
+            "{} games of {} players, {} with {:?} rules, seeds {}..{}, bots {}\n\
+             average score  {:.2}\n\
+             win rate       {:.1}%\n\
+             bomb-out rate  {:.1}%\n\
+             average turns  {:.1}\n\
+             \n\
+             score distribution\n",
+            self.games,
+            self.players,
+            self.variant.name(),
+            self.rules,
+            self.seeds.start,
+            self.seeds.end,
+            self.bots.join(","),
+            self.average_score,
+            100.0 * self.win_rate,
+            100.0 * self.bomb_out_rate,
+            self.average_turns,
+        );
+
+        let most = self
+            .score_distribution
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for (score, &count) in self.score_distribution.iter().enumerate() {
+            let bar = "#".repeat((count * 50).div_ceil(most));
+            text += &format!("{score:>4} {count:>6} {bar}\n");
+        }
+
+        for failure in &self.failures {
+            text += &format!("seed {} failed: {}\n", failure.seed, failure.error);
+        }
+        text
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (text, json, failed) = if options.solve {
+        let report = SolveReport::new(&options, run_seeds(&options, solve_game));
+        let json = serde_json::to_string_pretty(&report);
+        (report.to_text(), json, !report.failures.is_empty())
+    } else {
+        let max_score = options.variant.max_score();
+        let report = Report::new(&options, max_score, run_seeds(&options, run_game));
+        let json = serde_json::to_string_pretty(&report);
+        (report.to_text(), json, !report.failures.is_empty())
+    };
+    if options.json {
+        println!("{}", json.expect("reports are plain data"));
+    } else {
+        print!("{text}");
+    }
+
+    if !failed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> impl Iterator<Item = String> + '_ {
+        line.split_whitespace().map(str::to_string)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(args(
+            "--players 2 --variant rainbow --rules strict --seeds 5..10 --bots search,simple --json",
+        ))
+        .unwrap()
+        .unwrap();
+        assert_eq!(options.players, 2);
+        assert_eq!(options.variant, Variant::Rainbow);
+        assert_eq!(options.rules, RuleValidation::Strict);
+        assert_eq!(options.seeds, 5..10);
+        assert_eq!(options.bots, vec![BotKind::Search, BotKind::Simple]);
+        assert!(options.json);
+
+        let options = parse_args(args("--players 4 --bots random"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.bots, vec![BotKind::Random; 4]);
+        assert_eq!(options.variant, Variant::Standard);
+        assert_eq!(options.rules, RuleValidation::Lenient);
+
+        assert_eq!(parse_args(args("--players 9 --help")), Ok(None));
+
+        assert!(parse_args(args("--players 3 --bots simple,simple")).is_err());
+        assert!(parse_args(args("--seeds 10")).is_err());
+        assert!(parse_args(args("--bots clever")).is_err());
+        assert!(parse_args(args("--variant chess")).is_err());
+    }
+
+    #[test]
+    fn test_every_game_ends_with_an_outcome() {
+        let options = Options {
+            seeds: 0..20,
+            bots: vec![BotKind::Random; 3],
+            threads: 2,
+            ..Options::default()
+        };
+        let report = Report::new(&options, 25, run_seeds(&options, run_game));
+
+        assert!(report.failures.is_empty(), "{:?}", report.failures);
+        assert_eq!(report.games, 20);
+        assert_eq!(report.score_distribution.iter().sum::<usize>(), 20);
+    }
+
+    #[test]
+    fn test_panicking_game_fails_its_seed() {
+        fn panics_on_odd_seeds(_: &Options, seed: u64) -> Result<u64, String> {
+            assert!(seed.is_multiple_of(2), "odd seed");
+            Ok(seed)
+        }
+
+        let options = Options {
+            seeds: 0..6,
+            threads: 2,
+            ..Options::default()
+        };
+        let results = run_seeds(&options, panics_on_odd_seeds);
+
+        assert_eq!(results.len(), 6);
+        for (seed, result) in results {
+            match seed % 2 {
+                0 => assert_eq!(result, Ok(seed)),
+                _ => assert_eq!(result, Err("panicked: odd seed".to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_labels_every_seed() {
+        let options = Options {
+            players: 2,
+            seeds: 0..10,
+            solve: true,
+            threads: 2,
+            ..Options::default()
+        };
+        let report = SolveReport::new(&options, run_seeds(&options, solve_game));
+
+        assert!(report.failures.is_empty(), "{:?}", report.failures);
+        assert_eq!(report.perfect + report.impossible + report.unknown, 10);
+        for seed in &report.solved {
+            assert!(seed.best_score <= seed.upper_bound);
+        }
+    }
+}
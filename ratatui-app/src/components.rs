@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, iter};
 
 use itertools::Itertools;
 use shared::model::*;
@@ -34,6 +34,8 @@ impl CardKey for CardSuit {
             CardSuit::Yellow => "Y",
             CardSuit::White => "W",
             CardSuit::Blue => "B",
+            CardSuit::Rainbow => "M",
+            CardSuit::Black => "K",
         }
     }
 }
@@ -46,6 +48,7 @@ impl CardKey for CardFace {
             CardFace::Three => "3",
             CardFace::Four => "4",
             CardFace::Five => "5",
+            CardFace::Start => "S",
         }
     }
 }
@@ -70,6 +73,8 @@ pub fn colorize_suit(suit: CardSuit) -> Color {
         CardSuit::Yellow => YELLOW_SUIT,
         CardSuit::White => WHITE_SUIT,
         CardSuit::Blue => BLUE_SUIT,
+        CardSuit::Rainbow => RAINBOW_SUIT,
+        CardSuit::Black => BLACK_SUIT,
     }
 }
 
@@ -78,6 +83,8 @@ pub static GREEEN_SUIT: Color = Color::Rgb(113, 244, 120);
 pub static YELLOW_SUIT: Color = Color::Rgb(238, 249, 137);
 pub static WHITE_SUIT: Color = Color::Rgb(255, 255, 255);
 pub static BLUE_SUIT: Color = Color::Rgb(90, 90, 245);
+pub static RAINBOW_SUIT: Color = Color::Rgb(205, 110, 235);
+pub static BLACK_SUIT: Color = Color::Rgb(130, 130, 145);
 
 // Merged with the background color, but for performance reasons just made it static.
 pub static RED_SUIT_DIM: Color = Color::Rgb(235 / 2 + 36 / 2, 90 / 2 + 37 / 2, 78 / 2 + 47 / 2);
@@ -87,6 +94,10 @@ pub static YELLOW_SUIT_DIM: Color =
     Color::Rgb(238 / 2 + 36 / 2, 249 / 2 + 37 / 2, 137 / 2 + 47 / 2);
 pub static WHITE_SUIT_DIM: Color = Color::Rgb(255 / 2 + 36 / 2, 255 / 2 + 37 / 2, 255 / 2 + 47 / 2);
 pub static BLUE_SUIT_DIM: Color = Color::Rgb(90 / 2 + 36 / 2, 90 / 2 + 37 / 2, 245 / 2 + 47 / 2);
+pub static RAINBOW_SUIT_DIM: Color =
+    Color::Rgb(205 / 2 + 36 / 2, 110 / 2 + 37 / 2, 235 / 2 + 47 / 2);
+pub static BLACK_SUIT_DIM: Color =
+    Color::Rgb(130 / 2 + 36 / 2, 130 / 2 + 37 / 2, 145 / 2 + 47 / 2);
 
 pub fn colorize_suit_dim(suit: CardSuit) -> Color {
     match suit {
@@ -95,6 +106,8 @@ pub fn colorize_suit_dim(suit: CardSuit) -> Color {
         CardSuit::Yellow => YELLOW_SUIT_DIM,
         CardSuit::White => WHITE_SUIT_DIM,
         CardSuit::Blue => BLUE_SUIT_DIM,
+        CardSuit::Rainbow => RAINBOW_SUIT_DIM,
+        CardSuit::Black => BLACK_SUIT_DIM,
     }
 }
 
@@ -113,6 +126,8 @@ pub enum CardRenderState {
 pub struct CardProps {
     pub card: CardNodeProps,
     pub state: CardRenderState,
+    /// Marks critical and trash cards, only known for cards the player can see
+    pub class: Option<CardClass>,
 }
 
 #[derive(Clone, Copy)]
@@ -134,6 +149,7 @@ pub struct SlotNodeProps {
     pub unique_hints: Vec<Hint>,
     pub unique_not_hints: Vec<Hint>,
     pub all_hints: Vec<Hint>,
+    pub possible_suits: Vec<CardSuit>,
 }
 pub enum PlayerRenderState {
     CurrentTurn,
@@ -143,17 +159,31 @@ pub enum PlayerRenderState {
 
 pub struct PlayerNodeProps {
     pub name: String,
+    pub suits: Vec<CardSuit>,
     pub hand: Vec<SlotNodeProps>,
     pub state: PlayerRenderState,
     pub hint_mode: HintMode,
 }
 
 pub struct BoardProps {
-    pub highest_played_card_for_suit: HashMap<CardSuit, CardFace>,
+    pub suits: Vec<CardSuit>,
+    /// Played faces of each suit, in the order they were played
+    pub played_faces_for_suit: HashMap<CardSuit, Vec<CardFace>>,
+    /// Only filled for variants where a stack can be built both ways
+    pub stack_direction_for_suit: HashMap<CardSuit, StackDirection>,
     pub discards: Vec<Card>,
     pub draw_remaining: usize,
     pub hints_remaining: usize,
+    /// Clue starved games can have half a clue on top of the whole ones
+    pub half_hint: bool,
     pub fuse_remaining: usize,
+    /// Played cards whose result is hidden, see `Variant::hides_play_results`
+    pub cards_in_hole: usize,
+    /// Only set once the draw pile is empty
+    pub turns_remaining: Option<u8>,
+    /// Best score still reachable given the discarded cards
+    pub max_score: usize,
+    pub pace: Option<isize>,
 }
 
 pub fn padding(size: f32) -> taffy::Rect<taffy::LengthPercentage> {
@@ -182,6 +212,7 @@ pub fn card_node(card_props: &CardProps) -> Node<'static> {
     let &CardProps {
         card: card_detail_props,
         state: card_render_state,
+        class: card_class,
     } = card_props;
 
     let color = match card_detail_props {
@@ -197,6 +228,12 @@ pub fn card_node(card_props: &CardProps) -> Node<'static> {
         CardNodeProps::Discarded(Card { face: f, .. }) => f.key().not_bold().fg(color),
     };
 
+    let marker = match card_class {
+        Some(CardClass::Critical) => "!".bold().fg(Color::Red),
+        Some(CardClass::Trash) => "×".not_bold().fg(DIM_TEXT),
+        _ => "".into(),
+    };
+
     Block::new()
         .borders(Borders::ALL)
         .border_type(match card_render_state {
@@ -208,6 +245,8 @@ pub fn card_node(card_props: &CardProps) -> Node<'static> {
             _ => default_style().add_modifier(Modifier::BOLD).fg(color),
         })
         .style(default_style())
+        .title_bottom(marker)
+        .title_alignment(Alignment::Center)
         .children(
             LayoutStyle {
                 flex_direction: taffy::FlexDirection::Row,
@@ -243,6 +282,10 @@ pub fn hint_span_derive(hint: &Hint) -> Node<'static> {
 }
 
 pub fn hint_span(hint: &Hint) -> Node<'static> {
+    hint_key_span(hint).into()
+}
+
+pub fn hint_key_span(hint: &Hint) -> Span<'static> {
     match hint {
         Hint::IsNotSuit(suit) => Span::styled(
             suit.key().to_string(),
@@ -261,7 +304,23 @@ pub fn hint_span(hint: &Hint) -> Node<'static> {
             default_style().fg(ALMOST_WHITE).bold(),
         ),
     }
-    .into()
+}
+
+/// The hints a focused card received, one line per clue with its turn and giver
+pub fn clue_timeline_node(lines: Vec<Line<'static>>) -> Node<'static> {
+    Block::new()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(" clues ".set_style(default_style().fg(BLOCK_COLOR)))
+        .style(default_style().fg(BLOCK_COLOR).bg(BACKGROUND_COLOR))
+        .layout(LayoutStyle {
+            size: Size {
+                width: auto(),
+                height: length(lines.len() as f32 + 2.),
+            },
+            ..Block::default_layout()
+        })
+        .child(Text::from(lines))
 }
 
 pub fn rounded_block(title: Span<'static>) -> Block<'static> {
@@ -288,6 +347,23 @@ pub static BOARD_SUIT_ORDER: [CardSuit; 5] = [
     CardSuit::Yellow,
 ];
 
+/// Board order for the suits of a variant, suits outside of the base game go last
+pub fn board_suit_order(variant: Variant) -> Vec<CardSuit> {
+    let suits = variant.suits();
+
+    BOARD_SUIT_ORDER
+        .iter()
+        .copied()
+        .filter(|suit| suits.contains(suit))
+        .chain(
+            suits
+                .iter()
+                .copied()
+                .filter(|suit| !BOARD_SUIT_ORDER.contains(suit)),
+        )
+        .collect()
+}
+
 pub static CARD_FACE_ORDER: [CardFace; 5] = [
     CardFace::One,
     CardFace::Two,
@@ -464,24 +540,18 @@ pub fn player_node(player_props: PlayerNodeProps) -> Node<'static> {
                                             })
                                             .collect();
 
-                                        let possible_suits: Vec<_> = BOARD_SUIT_ORDER
-                                            .into_iter()
-                                            .filter(|possible_suit| {
-                                                if let Some(suit_hint) = s.suit_hint {
-                                                    suit_hint == Hint::IsSuit(*possible_suit)
-                                                } else {
-                                                    s.unique_not_hints.iter().all(|h| {
-                                                        h != &Hint::IsNotSuit(*possible_suit)
-                                                    })
-                                                }
-                                            })
-                                            .collect();
+                                        let possible_suits = &s.possible_suits;
 
                                         VStack::new().children(
                                             LayoutStyle {
                                                 size: Size {
                                                     width: length(1.),
-                                                    height: length(11.),
+                                                    height: length(
+                                                        (CARD_FACE_ORDER.len()
+                                                            + player_props.suits.len()
+                                                            + 1)
+                                                            as f32,
+                                                    ),
                                                 },
                                                 ..VStack::default_layout()
                                             },
@@ -496,7 +566,7 @@ pub fn player_node(player_props: PlayerNodeProps) -> Node<'static> {
                                                     Span::from(" ").into()
                                                 }
                                             }))
-                                            .chain(BOARD_SUIT_ORDER.iter().map(|s| {
+                                            .chain(player_props.suits.iter().map(|s| {
                                                 if possible_suits
                                                     .iter()
                                                     .any(|possible_suit| s == possible_suit)
@@ -563,38 +633,51 @@ pub fn played_cards_tree(board_props: &BoardProps) -> Node<'static> {
             justify_content: Some(JustifyContent::Center),
             ..HStack::default_layout()
         },
-        BOARD_SUIT_ORDER
+        board_props
+            .suits
             .iter()
             .map(|s| {
-                let highest = board_props
-                    .highest_played_card_for_suit
-                    .get(&s)
-                    .and_then(|highest| CARD_FACE_ORDER.iter().find_position(|&c| c == highest));
+                let played_faces = board_props
+                    .played_faces_for_suit
+                    .get(s)
+                    .filter(|faces| !faces.is_empty());
+                let direction = board_props.stack_direction_for_suit.get(s).map(|d| {
+                    Span::from(match d {
+                        StackDirection::Undecided => " ↕ ",
+                        StackDirection::Up => " ↑ ",
+                        StackDirection::Down => " ↓ ",
+                    })
+                    .fg(colorize_suit(*s))
+                    .into()
+                });
 
                 VStack::new().children(
                     LayoutStyle {
                         position: taffy::Position::Relative,
                         size: Size {
                             width: length(3.),
-                            height: length(3.),
+                            height: length(if direction.is_some() { 4. } else { 3. }),
                         },
                         ..VStack::default_layout()
                     },
-                    if let Some((highest_index, _)) = highest {
-                        vec![card_pile(
-                            FlexDirection::Column,
-                            (0..=highest_index)
-                                .map(|i| {
-                                    CardNodeProps::SomeCard(Some(CARD_FACE_ORDER[i]), Some(*s))
-                                })
-                                .collect_vec(),
-                        )]
-                    } else {
-                        vec![card_node(&CardProps {
-                            card: CardNodeProps::Empty,
-                            state: CardRenderState::Default,
-                        })]
-                    },
+                    direction
+                        .into_iter()
+                        .chain(iter::once(if let Some(played_faces) = played_faces {
+                            card_pile(
+                                FlexDirection::Column,
+                                played_faces
+                                    .iter()
+                                    .map(|f| CardNodeProps::SomeCard(Some(*f), Some(*s)))
+                                    .collect_vec(),
+                            )
+                        } else {
+                            card_node(&CardProps {
+                                card: CardNodeProps::Empty,
+                                state: CardRenderState::Default,
+                                class: None,
+                            })
+                        }))
+                        .collect_vec(),
                 )
             })
             .collect_vec(),
@@ -602,7 +685,7 @@ pub fn played_cards_tree(board_props: &BoardProps) -> Node<'static> {
 }
 
 pub fn board_stats_node_tree(board_props: &BoardProps) -> Node<'static> {
-    fn key_value_pairs<const N: usize>(pairs: [(String, String); N]) -> Node<'static> {
+    fn key_value_pairs(pairs: Vec<(String, String)>) -> Node<'static> {
         HStack::new().children(
             LayoutStyle {
                 gap: Size {
@@ -637,17 +720,34 @@ pub fn board_stats_node_tree(board_props: &BoardProps) -> Node<'static> {
         )
     }
 
-    let data = [
-        (
-            "hints:".to_string(),
-            "\u{f017} ".repeat(board_props.hints_remaining as usize),
-        ),
-        (
-            "bombs:".to_string(),
-            "\u{f0691} ".repeat(board_props.fuse_remaining as usize),
-        ),
-    ];
-    key_value_pairs(data)
+    let hints = (
+        "hints:".to_string(),
+        "\u{f017} ".repeat(board_props.hints_remaining)
+            + if board_props.half_hint { "½" } else { "" },
+    );
+    let bombs = (
+        "bombs:".to_string(),
+        "\u{f0691} ".repeat(board_props.fuse_remaining),
+    );
+
+    let max_score = ("max score:".to_string(), board_props.max_score.to_string());
+    let pace = board_props
+        .pace
+        .map(|pace| ("pace:".to_string(), pace.to_string()));
+    let hole = (board_props.cards_in_hole > 0)
+        .then(|| ("hole:".to_string(), board_props.cards_in_hole.to_string()));
+    let last_round = board_props
+        .turns_remaining
+        .map(|turns| ("turns left:".to_string(), turns.to_string()));
+
+    key_value_pairs(
+        [hints, bombs, max_score]
+            .into_iter()
+            .chain(pace)
+            .chain(hole)
+            .chain(last_round)
+            .collect(),
+    )
 }
 
 pub fn card_pile(direction: FlexDirection, card_props: Vec<CardNodeProps>) -> Node<'static> {
@@ -704,6 +804,7 @@ pub fn card_pile(direction: FlexDirection, card_props: Vec<CardNodeProps>) -> No
                     [card_node(&CardProps {
                         card,
                         state: CardRenderState::Default,
+                        class: None,
                     })],
                 )
             })
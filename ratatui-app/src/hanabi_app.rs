@@ -19,6 +19,7 @@ use taffy::{
 
 use crate::{
     components::*,
+    glyphs::{EQUALS_SIGN, NOT_EQUALS},
     key_code::KeyCode,
     nodes::{
         GridStack, HStack, LayoutRect, LayoutSize, LayoutStyle, Node, NodeBuilder, Stack, VStack,
@@ -291,11 +292,18 @@ impl HanabiApp {
                 game_state_index: 0,
                 num_rounds: 0,
                 board_render_state: BoardProps {
-                    highest_played_card_for_suit: HashMap::new(),
+                    suits: BOARD_SUIT_ORDER.to_vec(),
+                    played_faces_for_suit: HashMap::new(),
+                    stack_direction_for_suit: HashMap::new(),
                     discards: vec![],
                     draw_remaining: 0,
                     hints_remaining: 0,
+                    half_hint: false,
                     fuse_remaining: 0,
+                    cards_in_hole: 0,
+                    turns_remaining: None,
+                    max_score: Variant::Standard.max_score(),
+                    pace: None,
                 },
                 players: players
                     .iter()
@@ -304,6 +312,7 @@ impl HanabiApp {
                         player_node_props(
                             PlayerIndex(index),
                             p.name.clone(),
+                            BOARD_SUIT_ORDER.to_vec(),
                             (0..5).into_iter().map(|_| None).collect_vec(),
                             PlayerRenderState::Default,
                             HintMode::NotHints,
@@ -311,6 +320,7 @@ impl HanabiApp {
                     })
                     .collect_vec(),
                 game_log: vec![],
+                clue_timeline: vec![],
             },
             legend_description,
             legend,
@@ -405,7 +415,14 @@ impl HanabiApp {
                                 .collect_vec(),
                         ),
                         board_node_tree(game_props.board_render_state),
-                    ]),
+                    ])
+                    .into_iter()
+                    .chain(
+                        Some(game_props.clue_timeline)
+                            .filter(|lines| !lines.is_empty())
+                            .map(clue_timeline_node),
+                    )
+                    .collect_vec(),
                 ),
                 self.render_game_log(game_props.game_log.iter().map(|log| log.log_entries.clone()).flatten().collect_vec())
                     .append_layout(|layout| LayoutStyle {
@@ -584,7 +601,7 @@ impl HanabiApp {
         game_state: &GameStateSnapshot,
         players: &Vec<OnlinePlayer>,
     ) -> (String, Vec<LegendItem>) {
-        if let Some(outcome) = &game_state.outcome {
+        if let GameStatus::Finished(outcome) = &game_state.status {
             return (
                 format!(
                     "The game has ended, you {}",
@@ -601,7 +618,8 @@ impl HanabiApp {
             );
         }
 
-       
+        let legal_actions = game_state.legal_actions();
+        let any_legal = |is_kind: fn(&PlayerAction) -> bool| legal_actions.iter().any(is_kind);
 
         fn readable_slot_index(SlotIndex(idx): SlotIndex) -> &'static str {
             match idx {
@@ -622,11 +640,11 @@ impl HanabiApp {
                         "{}'s turn",
                         players[game_state.current_turn_player_index.0].name
                     ),
-                    vec![LegendItem {
+                    any_legal(|a| matches!(a, PlayerAction::MoveSlot(_, _, _))).then(|| LegendItem {
                         desc: "Move Card".to_string(),
                         key_code: Char('m'),
                         action: AppAction::GameAction(GameAction::StartMove),
-                    }],
+                    }).into_iter().collect(),
                 )
             } else {
                 (
@@ -638,26 +656,22 @@ impl HanabiApp {
                             .fg(SELECTION_COLOR),
                     ),
                     [
-                        Some(LegendItem {
+                        any_legal(|a| matches!(a, PlayerAction::PlayCard(_))).then(|| LegendItem {
                             desc: "Play Card".to_string(),
                             key_code: Char('p'),
                             action: AppAction::GameAction(GameAction::StartPlay),
                         }),
-                        Some(LegendItem {
+                        any_legal(|a| matches!(a, PlayerAction::DiscardCard(_))).then(|| LegendItem {
                             desc: "Discard Card".to_string(),
                             key_code: Char('d'),
                             action: AppAction::GameAction(GameAction::StartDiscard),
                         }),
-    
-                        match game_state.remaining_hint_count {
-                            0 => None,
-                            _ => Some(LegendItem {
-                                desc: "Give Hint".to_string(),
-                                key_code: Char('h'),
-                                action: AppAction::GameAction(GameAction::StartHint),
-                            }),
-                        },
-                        Some(LegendItem {
+                        any_legal(|a| matches!(a, PlayerAction::GiveHint(_, _))).then(|| LegendItem {
+                            desc: "Give Hint".to_string(),
+                            key_code: Char('h'),
+                            action: AppAction::GameAction(GameAction::StartHint),
+                        }),
+                        any_legal(|a| matches!(a, PlayerAction::MoveSlot(_, _, _))).then(|| LegendItem {
                             desc: "Move Card".to_string(),
                             key_code: Char('m'),
                             action: AppAction::GameAction(GameAction::StartMove),
@@ -673,7 +687,7 @@ impl HanabiApp {
             CommandBuilder::Hinting(HintState::ChoosingPlayer) => (
                 "Choose a player index".to_string(),
                 (0..game_state.players.len())
-                    .filter(|&index| game_state.this_client_player_index.0 != index)
+                    .filter(|&index| legal_actions.iter().any(|a| matches!(a, PlayerAction::GiveHint(PlayerIndex(hinted), _) if *hinted == index)))
                     .map(|index| LegendItem {
                         desc: format!("{}", players[index].name),
                         key_code: Char(from_digit(index as u32 + 1, 10).unwrap()),
@@ -689,7 +703,7 @@ impl HanabiApp {
                     .collect_vec(),
             ),
 
-            CommandBuilder::Hinting(HintState::ChoosingHint { .. }) => (
+            CommandBuilder::Hinting(HintState::ChoosingHint { player_index }) => (
                 "Choose a suit or face hint".to_string(),
                 vec![
                     LegendItem {
@@ -747,7 +761,18 @@ impl HanabiApp {
                         key_code: Backspace,
                         action: AppAction::GameAction(GameAction::Undo),
                     },
-                ],
+                ]
+                .into_iter()
+                // only the hints touching at least one of their cards
+                .filter(|item| {
+                    let hinted_player = PlayerIndex(player_index as usize);
+                    match item.action {
+                        AppAction::GameAction(GameAction::SelectFace(face)) => legal_actions.contains(&PlayerAction::GiveHint(hinted_player, HintAction::SameFace(face))),
+                        AppAction::GameAction(GameAction::SelectSuit(suit)) => legal_actions.contains(&PlayerAction::GiveHint(hinted_player, HintAction::SameSuit(suit))),
+                        _ => true,
+                    }
+                })
+                .collect_vec(),
             ),
 
             CommandBuilder::PlayingCard(CardState::ChoosingCard { card_type })
@@ -765,7 +790,12 @@ impl HanabiApp {
                         description.to_string(),
                         hand.iter()
                             .enumerate()
-                            .filter(|(_, slot)| slot.is_some())
+                            .filter(|(index, _)| legal_actions.iter().any(|a| match (card_type, a) {
+                                (CardBuilderType::Play, PlayerAction::PlayCard(slot))
+                                | (CardBuilderType::Discard, PlayerAction::DiscardCard(slot))
+                                | (CardBuilderType::Move, PlayerAction::MoveSlot(_, slot, _)) => slot.0 == *index,
+                                _ => false,
+                            }))
                             .map(|(index, _)| LegendItem {
                                 desc: format!("{}", readable_slot_index(SlotIndex(index))),
                                 key_code: Char(from_digit(index as u32 + 1, 10).unwrap()),
@@ -852,6 +882,25 @@ struct GameLogRow {
     outcome: Option<GameOutcome>,
 }
 
+/// When and by whom the focused card was hinted, oldest clue first
+fn clue_timeline(history: &[(Hint, HintOrigin)], players: &[OnlinePlayer]) -> Vec<Line<'static>> {
+    history
+        .iter()
+        .map(|(hint, origin)| {
+            let name = &players[origin.giver.0].name;
+            Line::from(vec![
+                Span::raw(format!("{:<4}", format!("{}.", origin.turn + 1))).fg(DIM_TEXT),
+                Span::from(name[..8.min(name.len())].to_string()).fg(Color::White).bold(),
+                Span::raw(match hint {
+                    Hint::IsSuit(_) | Hint::IsFace(_) => format!(" {} ", EQUALS_SIGN),
+                    Hint::IsNotSuit(_) | Hint::IsNotFace(_) => format!(" {} ", NOT_EQUALS),
+                }).fg(DIM_TEXT),
+                hint_key_span(hint),
+            ])
+        })
+        .collect()
+}
+
 fn generate_game_log(
     game_state: &GameStateSnapshot,
     log: &Vec<GameSnapshotEvent>,
@@ -914,6 +963,8 @@ fn generate_game_log(
             CardSuit::Yellow => "Y",
             CardSuit::White => "W",
             CardSuit::Blue => "B",
+            CardSuit::Rainbow => "M",
+            CardSuit::Black => "K",
             // CardSuit::Red => "\u{f0b19}",
             // CardSuit::Green => "\u{f0b0e}",
             // CardSuit::Yellow => "\u{f0b20}",
@@ -932,6 +983,7 @@ fn generate_game_log(
             CardFace::Three => "3",
             CardFace::Four => "4",
             CardFace::Five => "5",
+            CardFace::Start => "S",
             // CardFace::One => "\u{f03a6}",
             // CardFace::Two => "\u{f03a9}",
             // CardFace::Three => "\u{f03ac}",
@@ -942,21 +994,25 @@ fn generate_game_log(
         .bold()
     }
 
-    fn card_played(effects: &Vec<GameEffect>) -> Card {
+    fn card_played(effects: &[GameEffect]) -> Span<'static> {
         let card_played = effects.iter().find_map(|effect| match effect {
             Eff::PlaceOnBoard(card) => Some(card),
             Eff::AddToDiscard(card) => Some(card),
             _ => None,
         });
 
-        *card_played.unwrap()
+        // Hidden when the card went face down into the hole
+        match card_played {
+            Some(c) => card(*c),
+            None => "??".fg(DIM_TEXT),
+        }
     }
 
     fn hint_slots(effects: &Vec<GameEffect>) -> Vec<usize> {
         effects
             .iter()
             .filter_map(|e| match e {
-                Eff::HintCard(_, SlotIndex(index), Hint::IsFace(_) | Hint::IsSuit(_)) => {
+                Eff::HintCard(_, SlotIndex(index), Hint::IsFace(_) | Hint::IsSuit(_), _) => {
                     Some(*index)
                 }
                 _ => None,
@@ -1004,7 +1060,7 @@ fn generate_game_log(
     fn hint_count(effects: &Vec<GameEffect>) -> usize {
         effects
             .iter()
-            .filter(|e| matches!(e, Eff::HintCard(_, _, _)))
+            .filter(|e| matches!(e, Eff::HintCard(_, _, _, _)))
             .count()
     }
 
@@ -1012,6 +1068,7 @@ fn generate_game_log(
         let result = effects.iter().find_map(|effect| match effect {
             Eff::BurnFuse => Some(" \u{f1052} \u{f0691}"),
             Eff::IncHint => Some(" \u{f15cb} \u{f017}"),
+            Eff::IncHalfHint => Some(" \u{f15cb} ½\u{f017}"),
             _ => None,
         });
 
@@ -1100,7 +1157,7 @@ fn generate_game_log(
                     Span::raw(" plays "),
                     slot(*slot_index),
                     Span::raw(" "),
-                    card(card_played(&effects)),
+                    card_played(effects),
                     result_span(&effects),
                 ]
                 .to_vec()),
@@ -1121,7 +1178,7 @@ fn generate_game_log(
                     Span::raw(" dumps "),
                     slot(*slot_index),
                     Span::raw(" "),
-                    card(card_played(&effects)),
+                    card_played(effects),
                     result_span(&effects),
                 ]
                 .to_vec()),
@@ -1224,7 +1281,7 @@ fn generate_game_log(
                 // PlayerAction::DiscardCard(slot_index) => todo!(),
                 PlayerAction::GiveHint(player_index, _) if player_index == focussed_player_index => 
                     e.effects.iter().any(|effect| match effect {
-                        Eff::HintCard(player_index, slot_index, Hint::IsFace(_) | Hint::IsSuit(_)) if Some(focussed_card_num) == draw_number(focussed_player_index, *slot_index, &e.post_event_game_snapshot) => true,
+                        Eff::HintCard(player_index, slot_index, Hint::IsFace(_) | Hint::IsSuit(_), _) if Some(focussed_card_num) == draw_number(focussed_player_index, *slot_index, &e.post_event_game_snapshot) => true,
                         _ => false,
                     }),
                 
@@ -1346,69 +1403,62 @@ fn game_action_item_tree(item: LegendItem) -> Node<'static> {
 }
 
 fn board_node_props(
-    played_cards: &Vec<Card>,
+    variant: Variant,
+    played_cards: &PlayedStacks,
     discard_pile: &Vec<Card>,
     draw_pile_count: u8,
     remaining_hint_count: u8,
+    has_half_hint: bool,
     remaining_bomb_count: u8,
 ) -> BoardProps {
-    let all_suits = [
-        CardSuit::Blue,
-        CardSuit::Green,
-        CardSuit::Red,
-        CardSuit::White,
-        CardSuit::Yellow,
-    ];
-
-    let highest_cards = all_suits
+    let all_suits = board_suit_order(variant);
+
+    let played_faces_for_suit = all_suits
         .iter()
-        .enumerate()
-        .map(|(_, &cur_suit)| {
-            let mut card_faces: Vec<_> = played_cards
-                .iter()
-                .filter_map(|c| match c {
-                    &Card { suit, face } if suit == cur_suit => Some(face),
-                    _ => None,
-                })
-                .collect();
-            card_faces.sort();
-            card_faces
-        })
-        .collect_vec();
+        .map(|&suit| (suit, played_cards.stack(suit).to_vec()))
+        .collect::<HashMap<CardSuit, Vec<CardFace>>>();
 
-    BoardProps {
-        highest_played_card_for_suit: all_suits
+    let stack_direction_for_suit = match variant {
+        Variant::UpOrDown => all_suits
             .iter()
-            .enumerate()
-            .filter_map(|(suit_index, &cur_suit)| {
-                let highest_face = highest_cards[suit_index].last().copied();
-                Some((cur_suit, highest_face?))
-            })
-            .collect::<HashMap<CardSuit, CardFace>>(),
+            .map(|&suit| (suit, variant.stack_direction(played_cards, suit)))
+            .collect(),
+        _ => HashMap::new(),
+    };
+
+    BoardProps {
+        suits: all_suits.clone(),
+        played_faces_for_suit,
+        stack_direction_for_suit,
         discards: discard_pile.clone(),
         draw_remaining: draw_pile_count as usize,
         hints_remaining: remaining_hint_count as usize,
+        half_hint: has_half_hint,
         fuse_remaining: remaining_bomb_count as usize,
+        cards_in_hole: 0,
+        turns_remaining: None,
+        max_score: variant.max_achievable_score(played_cards, discard_pile),
+        pace: None,
     }
 }
 
 fn slot_node_props(
+    variant: Variant,
     player_index: PlayerIndex,
     slot_index: SlotIndex,
     card_draw_num: usize,
     card: Option<Card>,
-    hints: Vec<Hint>,
+    hints: HintKnowledge,
     card_render_state: CardRenderState,
 ) -> SlotNodeProps {
-    let face_hint = hints.clone().into_iter().find_map(|h| match h {
-        Hint::IsFace(face) => Some(face),
-        _ => None,
-    });
+    let face_hint = hints.face_hints().next();
 
-    let suit_hint = hints.clone().into_iter().find_map(|h| match h {
-        Hint::IsSuit(suit) => Some(suit),
-        _ => None,
-    });
+    // a card touched by several colors can't be named after the first one (e.g. rainbow cards)
+    let possible_suits = variant.possible_suits(&hints);
+    let suit_hint = match possible_suits.as_slice() {
+        &[suit] => Some(suit),
+        _ => hints.suit_hints().next(),
+    };
 
     let (suit, face) = card
         .map(|c| (Some(c.suit), Some(c.face)))
@@ -1419,44 +1469,24 @@ fn slot_node_props(
         slot_index,
         card_id: card_draw_num,
 
-        all_hints: hints.clone(),
+        all_hints: hints.hints().collect(),
         card: CardProps {
             card: CardNodeProps::SomeCard(face, suit),
             state: card_render_state,
+            class: None,
         },
-        face_hint: hints.clone().into_iter().find(|h| match h {
-            Hint::IsFace(_) => true,
-            _ => false,
-        }), //face.map(|f| Hint::IsFace(f)),
-        suit_hint: hints.clone().into_iter().find(|h| match h {
-            Hint::IsSuit(_) => true,
-            _ => false,
-        }),
-        unique_hints: hints
-            .clone()
-            .into_iter()
-            .filter(|h| match h {
-                Hint::IsSuit(_) | Hint::IsFace(_) => true,
-                _ => false,
-            })
-            .unique()
-            .collect(),
-        unique_not_hints: hints
-            .clone()
-            .into_iter()
-            .filter(|h| match h {
-                Hint::IsNotSuit(_) | Hint::IsNotFace(_) => true,
-                _ => false,
-            })
-            .unique()
-            .collect(),
-  
+        face_hint: hints.face_hints().next().map(Hint::IsFace),
+        suit_hint: hints.suit_hints().next().map(Hint::IsSuit),
+        unique_hints: hints.positive_hints().collect(),
+        unique_not_hints: hints.negative_hints().collect(),
+        possible_suits,
     }
 }
 
 fn player_node_props(
     player_index: PlayerIndex,
     name: String,
+    suits: Vec<CardSuit>,
     hand: Vec<Option<SlotNodeProps>>,
     player_state: PlayerRenderState,
     hint_mode: HintMode,
@@ -1477,19 +1507,20 @@ fn player_node_props(
                 player_index: player_index,
                 slot_index: SlotIndex(index),
                 card_id: 0,
-                card: CardProps { card: CardNodeProps::Empty, state: CardRenderState::Default },
+                card: CardProps { card: CardNodeProps::Empty, state: CardRenderState::Default, class: None },
                 all_hints: vec![],
                 face_hint: None,
                 suit_hint: None,
                 unique_hints: vec![],
                 unique_not_hints: vec![],
-             
+                possible_suits: vec![],
             },
         })
         .collect_vec();
 
     PlayerNodeProps {
         name,
+        suits,
         hint_mode: hint_mode,
         hand: slot_props,
         state: player_state,
@@ -1535,6 +1566,7 @@ struct GameProps {
     board_render_state: BoardProps,
     players: Vec<PlayerNodeProps>,
     game_log: Vec<GameLogEntryProps>,
+    clue_timeline: Vec<Line<'static>>,
     num_rounds: usize,
     game_state_index: usize,
 }
@@ -1574,6 +1606,7 @@ impl From<HanabiApp> for GameProps {
                         //     (player_index, &snapshot)
                         // }).unwrap()
                     };
+                    let variant = game_state.game_config.variant;
                     // let selected_game_snapshot_event = log.iter().nth(selected_turn_index as usize).unwrap();
                     // let acting_player = selected_game_snapshot_event.current_turn_player_index;
                     // let selected_game_state = &selected_game_snapshot_event.post_event_game_snapshot;
@@ -1614,13 +1647,26 @@ impl From<HanabiApp> for GameProps {
                             Some(selected_turn_index),
                             app_state.card_focus
                         ),
-                        board_render_state: board_node_props(
-                            &selected_game_state.played_cards,
-                            &selected_game_state.discard_pile,
-                            selected_game_state.draw_pile_count,
-                            selected_game_state.remaining_hint_count,
-                            selected_game_state.remaining_bomb_count,
-                        ),
+                        clue_timeline: app_state.card_focus.and_then(|(player_index, draw_number)| {
+                            match &selected_game_state.players[player_index.0] {
+                                ClientPlayerView::Me { hand, .. } => hand.iter().flatten().find(|s| s.draw_number == draw_number).map(|s| s.hint_history.clone()),
+                                ClientPlayerView::Teammate { hand, .. } => hand.iter().flatten().find(|s| s.draw_number == draw_number).map(|s| s.hint_history.clone()),
+                            }
+                        }).map(|history| clue_timeline(&history, players)).unwrap_or_default(),
+                        board_render_state: BoardProps {
+                            cards_in_hole: selected_game_state.cards_in_hole,
+                            turns_remaining: selected_game_state.status.turns_remaining(),
+                            pace: Some(selected_game_state.pace()),
+                            ..board_node_props(
+                                variant,
+                                &selected_game_state.played_cards,
+                                &selected_game_state.discard_pile,
+                                selected_game_state.draw_pile_count,
+                                selected_game_state.remaining_hint_count,
+                                selected_game_state.has_half_hint,
+                                selected_game_state.remaining_bomb_count,
+                            )
+                        },
                         players: (0..players.len())
                             .into_iter()
                             .map(|player_index| {
@@ -1653,6 +1699,7 @@ impl From<HanabiApp> for GameProps {
                                 // slot_node_props(card.clone(), hints.clone())
                                 match &selected_game_state.players[player_index] {
                                     ClientPlayerView::Me { name, hand } => {
+                                        let hand_knowledge = selected_game_state.hand_knowledge();
                                         let mut slot_props : Vec<_> =  hand.iter().enumerate()
                                         .map(|(slot_index, h)| {
                                             let slot_index_focussed = app_state.card_focus.map(|(focussed_player_index, focussed_slot_index)| {
@@ -1660,11 +1707,21 @@ impl From<HanabiApp> for GameProps {
                                             }).unwrap_or(false);
 
                                             h.clone().map(|c| {
-                                                slot_node_props(PlayerIndex(player_index), SlotIndex(slot_index), c.draw_number, None, c.hints.clone(), match (&app_state.command.current_command, slot_index_focussed) {                    
+                                                let mut slot_props = slot_node_props(variant, PlayerIndex(player_index), SlotIndex(slot_index), c.draw_number, None, c.hints, match (&app_state.command.current_command, slot_index_focussed) {                    
                                                    ( &CommandBuilder::ConfirmingAction(PlayerAction::PlayCard(SlotIndex(selected_slot_index)) | PlayerAction::DiscardCard(SlotIndex(selected_slot_index))) | &CommandBuilder::MovingCard(MovingCardState::ChangeSlot {  from_slot_index : SlotIndex(selected_slot_index), ..}), _) if slot_index == selected_slot_index => CardRenderState::Highlighted ,
                                                    (_, true) => CardRenderState::Highlighted,
                                                     _ => CardRenderState::Default,                                           
-                                                }/* implement for teammates: choosing a card to play or discard */)
+                                                }/* implement for teammates: choosing a card to play or discard */);
+                                                // show what can be deduced from the visible cards, not just what was hinted
+                                                if let Some(Some(knowledge)) = hand_knowledge.get(slot_index) {
+                                                    if let CardNodeProps::SomeCard(face, suit) = slot_props.card.card {
+                                                        slot_props.card.card = CardNodeProps::SomeCard(
+                                                            knowledge.known_face().or(face),
+                                                            knowledge.known_suit().or(suit),
+                                                        );
+                                                    }
+                                                }
+                                                slot_props
                                             })
                                         })
                                         .collect();
@@ -1683,6 +1740,7 @@ impl From<HanabiApp> for GameProps {
                                         player_node_props(
                                             PlayerIndex(player_index),
                                         name.clone(),
+                                        board_suit_order(variant),
                                        slot_props,
                                         player_state,
                                         hint_mode,
@@ -1691,6 +1749,7 @@ impl From<HanabiApp> for GameProps {
                                     ClientPlayerView::Teammate { name, hand } => player_node_props(
                                         PlayerIndex(player_index),
                                         name.clone(),
+                                        board_suit_order(variant),
                                         hand.iter().enumerate()
                                             .map(|(slot_index, h)| {
                                                 h.clone().map(|s| {
@@ -1698,15 +1757,16 @@ impl From<HanabiApp> for GameProps {
                                                         focussed_player_index == PlayerIndex(player_index) && focussed_slot_index == s.draw_number
                                                     }).unwrap_or(false);
 
-                                                    slot_node_props(PlayerIndex(player_index), SlotIndex(slot_index), s.draw_number, Some(s.card), s.hints.clone(), match  (&app_state.command.current_command, slot_index_focussed) {                    
+                                                    let mut slot_props = slot_node_props(variant, PlayerIndex(player_index), SlotIndex(slot_index), s.draw_number, Some(s.card), s.hints, match  (&app_state.command.current_command, slot_index_focussed) {                    
                                                     (&CommandBuilder::ConfirmingAction(PlayerAction::GiveHint(PlayerIndex(hinting_player_index), hint_action)), _) if player_index == hinting_player_index => match hint_action {
-                                                            HintAction::SameSuit(suit) if s.card.suit == suit => CardRenderState::Highlighted,
-                                                            HintAction::SameFace(face) if s.card.face == face => CardRenderState::Highlighted,
+                                                            hint_action if variant.hint_touches(&s.card, &hint_action) => CardRenderState::Highlighted,
                                                             _ => CardRenderState::Default,
                                                         },
                                                         (_, true) => CardRenderState::Highlighted,
                                                         _ => CardRenderState::Default,                                           
-                                                    })
+                                                    });
+                                                    slot_props.card.class = Some(selected_game_state.classify_card(&s.card));
+                                                    slot_props
                                                 })
                                             })
                                             .collect(),
@@ -1725,18 +1785,15 @@ impl From<HanabiApp> for GameProps {
                     ..
                 } => {
                     let selected_turn_index = game_state.num_rounds.saturating_sub(app_state.game_state_selection as u8);
-                    let merged_game_log = revealed_game_log.log.clone().into_iter().group_by(|event| event.current_turn_count).into_iter().map(|(t, g)| {
-                        let events = g.collect_vec();
-                        let last_event = events.last().unwrap().clone();
-                        // let events = events.into_iter().map(|event| game_log_lines(event, GameLogRenderState::Default)).flatten().collect_vec();
-                        last_event
-                    
-                    }).into_iter().collect_vec();
+                    // how many events were played by the end of each turn
+                    let merged_game_log = revealed_game_log.log.iter().enumerate().group_by(|(_, event)| event.current_turn_count).into_iter().map(|(_, g)| {
+                        g.last().unwrap().0 + 1
+                    }).collect_vec();
                     
-                    let (acting_player, selected_game_state) = 
-                        merged_game_log.iter().nth(selected_turn_index as usize - 1).map(|e| {
-                            (e.post_event_game_state.current_player_index() , &e.post_event_game_state)
-                        }).unwrap();
+                    let selected_game_state = revealed_game_log.state_after(merged_game_log[selected_turn_index as usize - 1]);
+                    let acting_player = selected_game_state.current_player_index();
+                    let selected_game_state = &selected_game_state;
+                    let variant = selected_game_state.config.variant;
 
                     GameProps {
                         num_rounds: revealed_game_log.current_game_state().turn as usize,
@@ -1751,13 +1808,22 @@ impl From<HanabiApp> for GameProps {
                             Some(selected_turn_index),
                             app_state.card_focus
                         ),
-                        board_render_state: board_node_props(
-                            &selected_game_state.played_cards,
-                            &selected_game_state.discard_pile,
-                            selected_game_state.draw_pile.len() as u8,
-                            selected_game_state.remaining_hint_count,
-                            selected_game_state.remaining_bomb_count,
-                        ),
+                        clue_timeline: app_state.card_focus.and_then(|(player_index, draw_number)| {
+                            selected_game_state.players[player_index.0].hand.iter().flatten().find(|s| s.draw_number == draw_number).map(|s| s.hint_history.clone())
+                        }).map(|history| clue_timeline(&history, players)).unwrap_or_default(),
+                        board_render_state: BoardProps {
+                            turns_remaining: selected_game_state.turns_remaining(),
+                            pace: Some(selected_game_state.pace()),
+                            ..board_node_props(
+                                variant,
+                                &selected_game_state.played_cards,
+                                &selected_game_state.discard_pile,
+                                selected_game_state.draw_pile.len() as u8,
+                                selected_game_state.remaining_hint_count,
+                                selected_game_state.has_half_hint,
+                                selected_game_state.remaining_bomb_count,
+                            )
+                        },
                         players: (0..players.len())
                             .into_iter()
                             .map(|player_index| {
@@ -1770,6 +1836,7 @@ impl From<HanabiApp> for GameProps {
                                 player_node_props(
                                     PlayerIndex(player_index),
                                     players[player_index].name.clone(),
+                                    board_suit_order(variant),
                                     selected_game_state.players[player_index]
                                         .hand
                                         .iter()
@@ -1779,7 +1846,7 @@ impl From<HanabiApp> for GameProps {
                                                 focussed_player_index == PlayerIndex(player_index) && Some(focussed_slot_index) == h.as_ref().map(|c| c.draw_number)
                                             }).unwrap_or(false);
 
-                                            slot_node_props(PlayerIndex(player_index), SlotIndex(slot_index), c.draw_number ,Some(c.card), c.hints.clone(), match slot_index_focussed {
+                                            slot_node_props(variant, PlayerIndex(player_index), SlotIndex(slot_index), c.draw_number ,Some(c.card), c.hints, match slot_index_focussed {
                                              true => CardRenderState::Highlighted ,
                                              false => CardRenderState::Default
                                         })
@@ -1803,6 +1870,52 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_hint_menu_never_offers_the_start_card() {
+        let players = ["p1", "p2"]
+            .map(|name| OnlinePlayer {
+                name: name.into(),
+                connection_status: ConnectionStatus::Connected,
+                is_host: false,
+            })
+            .to_vec();
+        let mut app = HanabiApp::new(HanabiClient::Connecting);
+        app.command.current_command =
+            CommandBuilder::Hinting(HintState::ChoosingHint { player_index: 1 });
+
+        for variant in [Variant::Standard, Variant::UpOrDown] {
+            let mut game_state = generate_minimal_test_game_state();
+            game_state.game_config.variant = variant;
+            game_state.players[1] = ClientPlayerView::Teammate {
+                name: "p2".to_string(),
+                hand: [CardFace::Start, CardFace::One]
+                    .into_iter()
+                    .map(|face| {
+                        Some(Slot {
+                            card: Card {
+                                face,
+                                suit: CardSuit::Red,
+                            },
+                            hints: HintKnowledge::new(),
+                            hint_history: vec![],
+                            draw_number: 0,
+                        })
+                    })
+                    .collect(),
+            };
+
+            let (_, items) = app.legend_for_command_state_game(&game_state, &players);
+            let faces = items
+                .iter()
+                .filter_map(|item| match item.action {
+                    AppAction::GameAction(GameAction::SelectFace(face)) => Some(face),
+                    _ => None,
+                })
+                .collect_vec();
+            assert_eq!(faces, vec![CardFace::One], "{variant:?}");
+        }
+    }
+
     // #[test]
     // fn test_game_ui() {
     //     use ratatui::prelude as ratatui;
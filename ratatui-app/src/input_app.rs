@@ -7,7 +7,9 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame, Terminal,
 };
+use shared::model::Variant;
 use std::{error::Error, iter, ops::ControlFlow};
+use strum::IntoEnumIterator;
 type BoxedResult<T> = std::result::Result<T, Box<dyn Error>>;
 use crate::key_code::KeyCode;
 
@@ -30,6 +32,8 @@ pub struct AppInput {
     pub session_id: Option<String>,
     pub session_join_url: Option<String>,
     pub server_address: String,
+    /// Variant of the game to create, picked with the arrow keys
+    pub variant: Variant,
 }
 
 impl Default for AppInput {
@@ -40,6 +44,7 @@ impl Default for AppInput {
             session_id: None,
             session_join_url: None,
             server_address: String::new(),
+            variant: Variant::default(),
         }
     }
 }
@@ -293,6 +298,7 @@ impl AppInput {
             session_id,
             session_join_url,
             server_address: url,
+            variant: Variant::default(),
         }
     }
 
@@ -327,6 +333,18 @@ impl AppInput {
                     }
                     _ => {}
                 },
+                KeyCode::Left | KeyCode::Right if self.session_id.is_none() => {
+                    let variants = Variant::iter().collect::<Vec<_>>();
+                    let index = variants
+                        .iter()
+                        .position(|variant| *variant == self.variant)
+                        .unwrap_or(0);
+                    let offset = match key_code {
+                        KeyCode::Left => variants.len() - 1,
+                        _ => 1,
+                    };
+                    self.variant = variants[(index + offset) % variants.len()];
+                }
                 KeyCode::Char(c) => match self.input_mode {
                     InputMode::EditingDisplayName => self.display_name.push(c),
                     // InputMode::EditingSessionId => self.session_id.push(c),
@@ -416,6 +434,15 @@ impl AppInput {
         .into_iter()
         .zip(layout.inputs[1]);
 
+        let variant_text = input_row(
+            "Variant (use the arrow keys to change it): ".to_string(),
+            "Variant".to_string(),
+            format!("< {} >", self.variant.name()),
+            false,
+        )
+        .into_iter()
+        .zip(layout.inputs[1]);
+
         // let server_address_text = input_row(
         //     "Enter the server URL: ".to_string(),
         //     "URL".to_string(),
@@ -434,9 +461,11 @@ impl AppInput {
                     });
             }
             None => {
-                display_name_text.for_each(|(text, rect)| {
-                    f.render_widget(text, rect);
-                });
+                display_name_text
+                    .chain(variant_text)
+                    .for_each(|(text, rect)| {
+                        f.render_widget(text, rect);
+                    });
             }
         }
 
@@ -579,4 +608,26 @@ mod tests {
 
         println!("{:#?}", input_layout);
     }
+
+    #[test]
+    fn test_arrow_keys_pick_the_variant() {
+        let mut app_input = AppInput::default();
+        assert!(app_input
+            .handle_event(KeyCode::Right)
+            .unwrap()
+            .is_continue());
+        assert_eq!(app_input.variant, Variant::Rainbow);
+        assert!(app_input.handle_event(KeyCode::Left).unwrap().is_continue());
+        assert!(app_input.handle_event(KeyCode::Left).unwrap().is_continue());
+        assert_eq!(app_input.variant, Variant::ThrowItInAHole);
+
+        // joining plays the variant the game was created with
+        let mut app_input =
+            AppInput::new(String::new(), Some("game".to_string()), None, String::new());
+        assert!(app_input
+            .handle_event(KeyCode::Right)
+            .unwrap()
+            .is_continue());
+        assert_eq!(app_input.variant, Variant::Standard);
+    }
 }
@@ -7,7 +7,7 @@ pub fn generate_minimal_test_game_state() -> GameStateSnapshot {
     GameStateSnapshot {
         this_client_player_index: PlayerIndex(0),
         draw_pile_count: 0,
-        played_cards: vec![],
+        played_cards: PlayedStacks::new(),
         discard_pile: vec![],
         players: vec![
             ClientPlayerView::Me {
@@ -21,10 +21,13 @@ pub fn generate_minimal_test_game_state() -> GameStateSnapshot {
         ],
         remaining_bomb_count: 1,
         remaining_hint_count: 1,
+        has_half_hint: false,
         current_turn_player_index: PlayerIndex(0),
         num_rounds: 0,
         last_turn: None,
         outcome: None,
+        status: GameStatus::Playing,
+        cards_in_hole: 0,
         game_config: GameConfig {
             num_players: 2,
             hand_size: 5,
@@ -32,6 +35,10 @@ pub fn generate_minimal_test_game_state() -> GameStateSnapshot {
             num_hints: 8,
             starting_player: PlayerIndex(0),
             seed: 0,
+            variant: Variant::Standard,
+            clue_economy: ClueEconomy::default(),
+            rules: RuleValidation::Lenient,
+            end_when_perfect_lost: false,
         },
     }
 }
@@ -61,15 +68,19 @@ pub fn generate_test_game_state() -> GameStateSnapshot {
                 face: CardFace::Five,
                 suit: CardSuit::Green,
             },
-        ],
+        ]
+        .into(),
         discard_pile: todo!(),
         players: todo!(),
         remaining_bomb_count: todo!(),
         remaining_hint_count: todo!(),
+        has_half_hint: false,
         current_turn_player_index: todo!(),
         num_rounds: todo!(),
         last_turn: todo!(),
         outcome: todo!(),
+        status: todo!(),
+        cards_in_hole: 0,
         // log: todo!(),
         game_config: todo!(),
     }
@@ -217,7 +228,8 @@ pub fn generate_example_panic_case_1() -> HanabiGame {
                     suit: Red,
                 },
             ]
-            .to_vec(),
+            .to_vec()
+            .into(),
             discard_pile: [Card {
                 face: Three,
                 suit: White,
@@ -228,7 +240,8 @@ pub fn generate_example_panic_case_1() -> HanabiGame {
                     name: "Mirza".to_string(),
                     hand: [
                         Some(HiddenSlot {
-                            hints: [].to_vec(),
+                            hints: [].into_iter().collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(HiddenSlot {
@@ -238,7 +251,9 @@ pub fn generate_example_panic_case_1() -> HanabiGame {
                                 IsNotFace(One),
                                 IsNotSuit(Blue),
                             ]
-                            .to_vec(),
+                            .into_iter()
+                            .collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(HiddenSlot {
@@ -248,11 +263,14 @@ pub fn generate_example_panic_case_1() -> HanabiGame {
                                 IsNotFace(One),
                                 IsNotSuit(Blue),
                             ]
-                            .to_vec(),
+                            .into_iter()
+                            .collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(HiddenSlot {
-                            hints: [IsSuit(Blue)].to_vec(),
+                            hints: [IsSuit(Blue)].into_iter().collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(HiddenSlot {
@@ -262,7 +280,9 @@ pub fn generate_example_panic_case_1() -> HanabiGame {
                                 IsNotFace(One),
                                 IsSuit(Blue),
                             ]
-                            .to_vec(),
+                            .into_iter()
+                            .collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                     ]
@@ -276,7 +296,8 @@ pub fn generate_example_panic_case_1() -> HanabiGame {
                                 face: Three,
                                 suit: Red,
                             },
-                            hints: [IsSuit(Red), IsNotFace(Two)].to_vec(),
+                            hints: [IsSuit(Red), IsNotFace(Two)].into_iter().collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(Slot {
@@ -290,7 +311,9 @@ pub fn generate_example_panic_case_1() -> HanabiGame {
                                 IsNotSuit(Red),
                                 IsNotFace(Two),
                             ]
-                            .to_vec(),
+                            .into_iter()
+                            .collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(Slot {
@@ -298,7 +321,8 @@ pub fn generate_example_panic_case_1() -> HanabiGame {
                                 face: One,
                                 suit: Green,
                             },
-                            hints: [].to_vec(),
+                            hints: [].into_iter().collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(Slot {
@@ -307,7 +331,9 @@ pub fn generate_example_panic_case_1() -> HanabiGame {
                                 suit: Green,
                             },
                             hints: [IsNotFace(One), IsFace(Five), IsNotSuit(Red), IsNotFace(Two)]
-                                .to_vec(),
+                                .into_iter()
+                                .collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(Slot {
@@ -321,7 +347,9 @@ pub fn generate_example_panic_case_1() -> HanabiGame {
                                 IsNotSuit(Red),
                                 IsNotFace(Two),
                             ]
-                            .to_vec(),
+                            .into_iter()
+                            .collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                     ]
@@ -331,10 +359,13 @@ pub fn generate_example_panic_case_1() -> HanabiGame {
             .to_vec(),
             remaining_bomb_count: 3,
             remaining_hint_count: 1,
+            has_half_hint: false,
             current_turn_player_index: PlayerIndex(1),
             num_rounds: 13,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            cards_in_hole: 0,
             // log: [
             // PlayerAction(PlayerIndex(0), GiveHint(PlayerIndex(1), SameFace(One))),
             // GameEffect(HintCard(PlayerIndex(1), SlotIndex(0), IsFace(One))),
@@ -450,6 +481,10 @@ pub fn generate_example_panic_case_1() -> HanabiGame {
                 num_hints: 8,
                 starting_player: PlayerIndex(0),
                 seed: 0,
+                variant: Variant::Standard,
+                clue_economy: ClueEconomy::default(),
+                rules: RuleValidation::Lenient,
+                end_when_perfect_lost: false,
             },
         },
     }
@@ -504,7 +539,8 @@ pub fn generate_example_panic_case_2() -> HanabiGame {
                     suit: Red,
                 },
             ]
-            .to_vec(),
+            .to_vec()
+            .into(),
             discard_pile: [Card {
                 face: Three,
                 suit: White,
@@ -515,7 +551,8 @@ pub fn generate_example_panic_case_2() -> HanabiGame {
                     name: "mirza".to_string(),
                     hand: [
                         Some(HiddenSlot {
-                            hints: [].to_vec(),
+                            hints: [].into_iter().collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(HiddenSlot {
@@ -525,7 +562,9 @@ pub fn generate_example_panic_case_2() -> HanabiGame {
                                 IsNotFace(One),
                                 IsNotSuit(Blue),
                             ]
-                            .to_vec(),
+                            .into_iter()
+                            .collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(HiddenSlot {
@@ -535,11 +574,14 @@ pub fn generate_example_panic_case_2() -> HanabiGame {
                                 IsNotFace(One),
                                 IsNotSuit(Blue),
                             ]
-                            .to_vec(),
+                            .into_iter()
+                            .collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(HiddenSlot {
-                            hints: [IsSuit(Blue)].to_vec(),
+                            hints: [IsSuit(Blue)].into_iter().collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(HiddenSlot {
@@ -549,7 +591,9 @@ pub fn generate_example_panic_case_2() -> HanabiGame {
                                 IsNotFace(One),
                                 IsSuit(Blue),
                             ]
-                            .to_vec(),
+                            .into_iter()
+                            .collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                     ]
@@ -563,7 +607,8 @@ pub fn generate_example_panic_case_2() -> HanabiGame {
                                 face: Three,
                                 suit: Red,
                             },
-                            hints: [IsSuit(Red), IsNotFace(Two)].to_vec(),
+                            hints: [IsSuit(Red), IsNotFace(Two)].into_iter().collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(Slot {
@@ -577,7 +622,9 @@ pub fn generate_example_panic_case_2() -> HanabiGame {
                                 IsNotSuit(Red),
                                 IsNotFace(Two),
                             ]
-                            .to_vec(),
+                            .into_iter()
+                            .collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(Slot {
@@ -585,7 +632,8 @@ pub fn generate_example_panic_case_2() -> HanabiGame {
                                 face: One,
                                 suit: Green,
                             },
-                            hints: [].to_vec(),
+                            hints: [].into_iter().collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(Slot {
@@ -594,7 +642,9 @@ pub fn generate_example_panic_case_2() -> HanabiGame {
                                 suit: Green,
                             },
                             hints: [IsNotFace(One), IsFace(Five), IsNotSuit(Red), IsNotFace(Two)]
-                                .to_vec(),
+                                .into_iter()
+                                .collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(Slot {
@@ -608,7 +658,9 @@ pub fn generate_example_panic_case_2() -> HanabiGame {
                                 IsNotSuit(Red),
                                 IsNotFace(Two),
                             ]
-                            .to_vec(),
+                            .into_iter()
+                            .collect(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                     ]
@@ -618,10 +670,13 @@ pub fn generate_example_panic_case_2() -> HanabiGame {
             .to_vec(),
             remaining_bomb_count: 3,
             remaining_hint_count: 1,
+            has_half_hint: false,
             current_turn_player_index: PlayerIndex(1),
             num_rounds: 13,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            cards_in_hole: 0,
             // log: [
             // PlayerAction(PlayerIndex(0), GiveHint(PlayerIndex(1), SameFace(One))),
             // GameEffect(HintCard(PlayerIndex(1), SlotIndex(0), IsFace(One))),
@@ -947,6 +1002,10 @@ pub fn generate_example_panic_case_2() -> HanabiGame {
                 num_hints: 8,
                 starting_player: PlayerIndex(0),
                 seed: 0,
+                variant: Variant::Standard,
+                clue_economy: ClueEconomy::default(),
+                rules: RuleValidation::Lenient,
+                end_when_perfect_lost: false,
             },
         },
     }
@@ -0,0 +1,137 @@
+//! Memory and serialized size of a full game log, compared with keeping the
+//! whole game state after every event as the log used to, and size of the log a
+//! client is sent, compared with sending a snapshot per event.
+//!
+//! Run with `cargo bench -p shared --bench game_log`
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rand::rngs::StdRng;
+use serde::Serialize;
+use shared::bot::{play_game, Bot, SimpleBot};
+use shared::client_log::ClientGameLog;
+use shared::client_logic::GameLog;
+use shared::model::{GameConfig, GameEffect, GameState, PlayerAction, PlayerIndex};
+
+/// Keeps count of the heap bytes currently allocated
+struct CountingAlloc;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Heap bytes held by whatever `build` returns
+fn heap_size<T>(build: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let value = build();
+    (value, ALLOCATED.load(Ordering::Relaxed) - before)
+}
+
+/// The log as it used to be, with the state after each event next to it
+#[derive(Clone, Serialize)]
+struct LegacyGameLog {
+    config: GameConfig,
+    initial: GameState,
+    log: Vec<LegacyGameLogEvent>,
+}
+
+#[derive(Clone, Serialize)]
+struct LegacyGameLogEvent {
+    current_turn_count: u8,
+    current_turn_player_index: PlayerIndex,
+    event_player_index: PlayerIndex,
+    event_action: PlayerAction,
+    event_effects: Vec<GameEffect>,
+    post_event_game_state: GameState,
+}
+
+impl LegacyGameLog {
+    /// Runs the events again, keeping a copy of the state after each one as the
+    /// log used to do
+    fn new(game_log: &GameLog) -> Self {
+        let mut game_state = game_log.initial.clone();
+        let log = game_log
+            .log
+            .iter()
+            .map(|event| {
+                game_state.run_effects(event.event_effects.clone()).unwrap();
+                LegacyGameLogEvent {
+                    current_turn_count: event.current_turn_count,
+                    current_turn_player_index: event.current_turn_player_index,
+                    event_player_index: event.event_player_index,
+                    event_action: event.event_action,
+                    event_effects: event.event_effects.clone(),
+                    post_event_game_state: game_state.clone(),
+                }
+            })
+            .collect();
+        LegacyGameLog {
+            config: game_log.config.clone(),
+            initial: game_log.initial.clone(),
+            log,
+        }
+    }
+}
+
+fn json_size<T: Serialize>(value: &T) -> usize {
+    serde_json::to_vec(value).unwrap().len()
+}
+
+fn main() {
+    println!(
+        "{:<8} {:>7} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "players",
+        "events",
+        "heap before",
+        "heap after",
+        "json before",
+        "json after",
+        "sent before",
+        "sent after",
+    );
+
+    for num_players in 2..=5 {
+        let config = GameConfig::new(num_players, 42);
+        let mut bots = (0..num_players)
+            .map(|_| Box::new(SimpleBot::new()) as Box<dyn Bot>)
+            .collect::<Vec<_>>();
+        let game_log = play_game::<StdRng>(config, &mut bots).unwrap();
+        let legacy = LegacyGameLog::new(&game_log);
+
+        // the server's log, both layouts copied the same way
+        let (_, heap_before) = heap_size(|| legacy.clone());
+        let (_, heap_after) = heap_size(|| game_log.clone());
+
+        // what the first player is sent, a snapshot per event used to go on the wire
+        let names = (0..num_players)
+            .map(|index| format!("p{index}"))
+            .collect::<Vec<_>>();
+        let client_log = game_log.into_client_game_log(PlayerIndex(0), names);
+
+        println!(
+            "{:<8} {:>7} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            num_players,
+            game_log.log.len(),
+            heap_before,
+            heap_after,
+            json_size(&legacy),
+            json_size(&game_log),
+            json_size(&client_log),
+            json_size(&ClientGameLog::new(&client_log)),
+        );
+    }
+}
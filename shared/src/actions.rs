@@ -0,0 +1,360 @@
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+use crate::model::{
+    Card, CardFace, CardSuit, ClientPlayerView, GameConfig, GameState, GameStateSnapshot,
+    HintAction, PlayerAction, PlayerIndex, SlotIndex, Variant,
+};
+use crate::rules;
+
+/// Every hint a variant lets a player give
+fn variant_hints(variant: Variant) -> impl Iterator<Item = HintAction> {
+    CardSuit::iter()
+        .map(HintAction::SameSuit)
+        .chain(CardFace::numbered().map(HintAction::SameFace))
+        .filter(move |hint| variant.is_hint_allowed(hint))
+}
+
+/// Hints to a teammate touching at least one of their cards
+fn touching_hints(
+    variant: Variant,
+    teammate: PlayerIndex,
+    cards: &[Card],
+) -> impl Iterator<Item = PlayerAction> + '_ {
+    variant_hints(variant)
+        .filter(move |hint| rules::hint_touches_any(variant, hint, cards.iter().copied()))
+        .map(move |hint| PlayerAction::GiveHint(teammate, hint))
+}
+
+/// Moves of any card of a hand to any other filled slot
+fn slot_moves(player: PlayerIndex, filled_slots: &[SlotIndex]) -> Vec<PlayerAction> {
+    filled_slots
+        .iter()
+        .cartesian_product(filled_slots)
+        .filter(|(from, to)| from != to)
+        .map(|(&from, &to)| PlayerAction::MoveSlot(player, from, to))
+        .collect()
+}
+
+fn filled_slots<T>(hand: &[Option<T>]) -> Vec<SlotIndex> {
+    hand.iter()
+        .positions(|slot| slot.is_some())
+        .map(SlotIndex)
+        .collect()
+}
+
+/// What a player sees that decides the actions they can take, the engine and a
+/// client fill it in the same way
+struct TableView<'a> {
+    config: &'a GameConfig,
+    player: PlayerIndex,
+    is_finished: bool,
+    current_player: PlayerIndex,
+    remaining_hint_count: u8,
+    /// The player's own hand, only whether each slot holds a card
+    filled_slots: Vec<SlotIndex>,
+    /// The cards in the hand of every other player
+    teammates: Vec<(PlayerIndex, Vec<Card>)>,
+}
+
+impl TableView<'_> {
+    fn legal_actions(&self) -> Vec<PlayerAction> {
+        if self.is_finished {
+            return vec![];
+        }
+        let moves = slot_moves(self.player, &self.filled_slots);
+        if self.current_player != self.player {
+            return moves;
+        }
+
+        let can_discard = rules::can_discard(self.config, self.remaining_hint_count);
+        let hints = self
+            .teammates
+            .iter()
+            .filter(|_| rules::can_hint(self.remaining_hint_count))
+            .flat_map(|(teammate, cards)| touching_hints(self.config.variant, *teammate, cards));
+
+        self.filled_slots
+            .iter()
+            .map(|&slot| PlayerAction::PlayCard(slot))
+            .chain(
+                self.filled_slots
+                    .iter()
+                    .filter(|_| can_discard)
+                    .map(|&slot| PlayerAction::DiscardCard(slot)),
+            )
+            .chain(hints)
+            .chain(moves)
+            .collect()
+    }
+
+    /// Whether `legal_actions` lists the action, without listing them
+    fn is_legal(&self, action: &PlayerAction) -> bool {
+        let is_filled = |slot: &SlotIndex| self.filled_slots.contains(slot);
+        if self.is_finished {
+            return false;
+        }
+
+        match action {
+            PlayerAction::MoveSlot(owner, from, to) => {
+                *owner == self.player && from != to && is_filled(from) && is_filled(to)
+            }
+            _ if self.current_player != self.player => false,
+            PlayerAction::PlayCard(slot) => is_filled(slot),
+            PlayerAction::DiscardCard(slot) => {
+                is_filled(slot) && rules::can_discard(self.config, self.remaining_hint_count)
+            }
+            PlayerAction::GiveHint(teammate, hint) => {
+                let variant = self.config.variant;
+                rules::can_hint(self.remaining_hint_count)
+                    && variant.is_hint_allowed(hint)
+                    && self.teammates.iter().any(|(index, cards)| {
+                        index == teammate
+                            && rules::hint_touches_any(variant, hint, cards.iter().copied())
+                    })
+            }
+        }
+    }
+}
+
+impl GameState {
+    fn table_view(&self, player: PlayerIndex) -> Option<TableView<'_>> {
+        let hand = &self.players.get(player.0)?.hand;
+        Some(TableView {
+            config: &self.config,
+            player,
+            is_finished: self.status.is_finished(),
+            current_player: self.current_player_index(),
+            remaining_hint_count: self.remaining_hint_count,
+            filled_slots: filled_slots(hand),
+            teammates: self
+                .players
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index != player.0)
+                .map(|(index, teammate)| {
+                    let cards = teammate.hand.iter().flatten().map(|s| s.card).collect();
+                    (PlayerIndex(index), cards)
+                })
+                .collect(),
+        })
+    }
+
+    /// Every action the player can take right now. Hints go to teammates and touch at
+    /// least one card, and moving cards around one's own hand doesn't end the turn.
+    pub fn legal_actions(&self, player: PlayerIndex) -> Vec<PlayerAction> {
+        self.table_view(player)
+            .map_or(vec![], |table_view| table_view.legal_actions())
+    }
+
+    pub fn is_legal(&self, player: PlayerIndex, action: &PlayerAction) -> bool {
+        self.table_view(player)
+            .is_some_and(|table_view| table_view.is_legal(action))
+    }
+}
+
+impl GameStateSnapshot {
+    fn table_view(&self) -> Option<TableView<'_>> {
+        let me = self.this_client_player_index;
+        let Some(ClientPlayerView::Me { hand, .. }) = self.players.get(me.0) else {
+            return None;
+        };
+        Some(TableView {
+            config: &self.game_config,
+            player: me,
+            is_finished: self.status.is_finished(),
+            current_player: self.current_turn_player_index,
+            remaining_hint_count: self.remaining_hint_count,
+            filled_slots: filled_slots(hand),
+            teammates: self
+                .players
+                .iter()
+                .enumerate()
+                .filter_map(|(index, player)| match player {
+                    ClientPlayerView::Teammate { hand, .. } => {
+                        let cards = hand.iter().flatten().map(|s| s.card).collect();
+                        Some((PlayerIndex(index), cards))
+                    }
+                    ClientPlayerView::Me { .. } => None,
+                })
+                .collect(),
+        })
+    }
+
+    /// What this client can do right now, the same actions `GameState::legal_actions` lists
+    pub fn legal_actions(&self) -> Vec<PlayerAction> {
+        self.table_view()
+            .map_or(vec![], |table_view| table_view.legal_actions())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::client_logic::GameLog;
+    use crate::model::RuleValidation;
+
+    #[test]
+    fn test_legal_actions_follow_the_rules() {
+        let config = GameConfig::new(3, 1).with_rules(RuleValidation::Strict);
+        let game_state = GameState::start_with_seed::<StdRng>(&config).unwrap();
+        let actions = game_state.legal_actions(PlayerIndex(0));
+
+        // every hint with all the clues left, but no discarding
+        assert!(actions
+            .iter()
+            .all(|a| !matches!(a, PlayerAction::DiscardCard(_))));
+        assert_eq!(
+            actions
+                .iter()
+                .filter(|a| matches!(a, PlayerAction::PlayCard(_)))
+                .count(),
+            5
+        );
+        assert!(actions.iter().all(|action| match action {
+            PlayerAction::GiveHint(teammate, hint) => {
+                *teammate != PlayerIndex(0)
+                    && game_state.players[teammate.0]
+                        .hand
+                        .iter()
+                        .flatten()
+                        .any(|slot| hint.touches(&slot.card))
+            }
+            _ => true,
+        }));
+
+        // only moves are left to the players waiting for their turn
+        assert!(game_state
+            .legal_actions(PlayerIndex(1))
+            .iter()
+            .all(|a| matches!(a, PlayerAction::MoveSlot(PlayerIndex(1), _, _))));
+
+        let no_hints = GameState {
+            remaining_hint_count: 0,
+            ..game_state.clone()
+        };
+        assert!(no_hints
+            .legal_actions(PlayerIndex(0))
+            .iter()
+            .all(|a| !matches!(a, PlayerAction::GiveHint(_, _))));
+    }
+
+    #[test]
+    fn test_snapshot_agrees_with_game_state() {
+        let config = GameConfig::new(3, 5).with_rules(RuleValidation::Strict);
+        let mut game_log = GameLog::new::<StdRng>(config).unwrap();
+        let names = vec!["p1".to_string(), "p2".to_string(), "p3".to_string()];
+
+        for _ in 0..12 {
+            let game_state = game_log.current_game_state();
+            for index in 0..3 {
+                let snapshot = game_log.into_client_game_state(
+                    game_state.clone(),
+                    PlayerIndex(index),
+                    names.clone(),
+                );
+                assert_eq!(
+                    snapshot.legal_actions(),
+                    game_state.legal_actions(PlayerIndex(index))
+                );
+            }
+
+            let player = game_state.current_player_index();
+            let action = game_state
+                .legal_actions(player)
+                .into_iter()
+                .rev()
+                .find(|a| !matches!(a, PlayerAction::MoveSlot(_, _, _)))
+                .unwrap();
+            game_log.log(player, action).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_start_cards_are_never_hinted() {
+        assert!(Variant::Standard
+            .deck()
+            .iter()
+            .all(|card| card.face != CardFace::Start));
+
+        for variant in [Variant::Standard, Variant::UpOrDown] {
+            let config = GameConfig::new(2, 3).with_variant(variant);
+            let mut game_state = GameState::start_with_seed::<StdRng>(&config).unwrap();
+            for slot in game_state.players[1].hand.iter_mut().flatten() {
+                slot.card.face = CardFace::Start;
+            }
+
+            let hints = game_state
+                .legal_actions(PlayerIndex(0))
+                .into_iter()
+                .filter_map(|action| match action {
+                    PlayerAction::GiveHint(_, hint) => Some(hint),
+                    _ => None,
+                })
+                .collect_vec();
+            assert!(!hints.is_empty(), "{variant:?}");
+            assert!(
+                hints
+                    .iter()
+                    .all(|hint| matches!(hint, HintAction::SameSuit(_))),
+                "{variant:?}"
+            );
+        }
+    }
+
+    /// Every action of any shape, legal or not
+    fn every_action(num_players: usize, num_slots: usize) -> Vec<PlayerAction> {
+        let players = (0..num_players).map(PlayerIndex).collect_vec();
+        let slots = (0..num_slots).map(SlotIndex).collect_vec();
+
+        let plays = slots.iter().map(|&slot| PlayerAction::PlayCard(slot));
+        let discards = slots.iter().map(|&slot| PlayerAction::DiscardCard(slot));
+        let hints = players
+            .iter()
+            .cartesian_product(variant_hints(Variant::Standard).collect_vec())
+            .map(|(&player, hint)| PlayerAction::GiveHint(player, hint));
+        let moves = players
+            .iter()
+            .cartesian_product(slots.iter().cartesian_product(&slots))
+            .map(|(&player, (&from, &to))| PlayerAction::MoveSlot(player, from, to));
+        plays.chain(discards).chain(hints).chain(moves).collect()
+    }
+
+    #[test]
+    fn test_is_legal_agrees_with_legal_actions() {
+        for rules in [RuleValidation::Lenient, RuleValidation::Strict] {
+            let config = GameConfig::new(3, 2).with_rules(rules);
+            let mut game_log = GameLog::new::<StdRng>(config).unwrap();
+
+            while !game_log.current_game_state().status.is_finished() {
+                let game_state = game_log.current_game_state();
+                for index in 0..3 {
+                    let player = PlayerIndex(index);
+                    let legal_actions = game_state.legal_actions(player);
+                    for action in every_action(3, 6) {
+                        assert_eq!(
+                            game_state.is_legal(player, &action),
+                            legal_actions.contains(&action),
+                            "{action:?} for {player:?} with {rules:?} rules"
+                        );
+                    }
+                    for action in &legal_actions {
+                        assert!(game_state.check_actor(player, action).is_ok());
+                        assert!(game_state.play(*action).is_ok(), "{action:?}");
+                    }
+                }
+
+                let player = game_state.current_player_index();
+                let action = game_state
+                    .legal_actions(player)
+                    .into_iter()
+                    .filter(|a| !matches!(a, PlayerAction::MoveSlot(_, _, _)))
+                    .min_by_key(|a| !matches!(a, PlayerAction::DiscardCard(_)))
+                    .unwrap();
+                game_log.log(player, action).unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::client_logic::GameLog;
+use crate::model::{
+    CardClass, ClientPlayerView, GameConfig, GameError, GameSnapshotEvent, GameStateSnapshot,
+    HintAction, PlayerAction, PlayerIndex, SlotIndex,
+};
+
+/// Plays a seat from what that player can see: their snapshot and the game log so far.
+/// Only asked for an action on its own turn, while the game is still going.
+pub trait Bot {
+    fn choose_action(
+        &mut self,
+        snapshot: &GameStateSnapshot,
+        log: &[GameSnapshotEvent],
+    ) -> PlayerAction;
+}
+
+/// Actions that end the turn, moving cards around is left out
+fn turn_actions(snapshot: &GameStateSnapshot) -> Vec<PlayerAction> {
+    snapshot
+        .legal_actions()
+        .into_iter()
+        .filter(|action| !matches!(action, PlayerAction::MoveSlot(_, _, _)))
+        .collect()
+}
+
+/// Picks any legal action, to put the engine through its paces
+pub struct RandomBot {
+    rng: StdRng,
+}
+
+impl RandomBot {
+    pub fn new(seed: u64) -> Self {
+        RandomBot {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Bot for RandomBot {
+    fn choose_action(
+        &mut self,
+        snapshot: &GameStateSnapshot,
+        _log: &[GameSnapshotEvent],
+    ) -> PlayerAction {
+        *turn_actions(snapshot)
+            .choose(&mut self.rng)
+            .expect("a player always holds a card on their turn")
+    }
+}
+
+/// Plays a card it knows is playable, otherwise hints a playable card to a teammate,
+/// otherwise discards its oldest card without hints
+#[derive(Default)]
+pub struct SimpleBot;
+
+impl SimpleBot {
+    pub fn new() -> Self {
+        SimpleBot
+    }
+
+    /// A card that can only be playable, hints that contradict each other don't count
+    fn known_playable(snapshot: &GameStateSnapshot) -> Option<PlayerAction> {
+        snapshot
+            .hand_knowledge()
+            .into_iter()
+            .enumerate()
+            .find(|(_, knowledge)| {
+                knowledge.as_ref().is_some_and(|knowledge| {
+                    let mut possible_cards = knowledge.possible_cards().peekable();
+                    possible_cards.peek().is_some()
+                        && possible_cards
+                            .all(|card| snapshot.classify_card(&card) == CardClass::Playable)
+                })
+            })
+            .map(|(index, _)| PlayerAction::PlayCard(SlotIndex(index)))
+    }
+
+    /// A hint to the next teammate holding a playable card nobody hinted yet
+    fn playable_hint(
+        snapshot: &GameStateSnapshot,
+        legal_actions: &[PlayerAction],
+    ) -> Option<PlayerAction> {
+        let num_players = snapshot.players.len();
+        let me = snapshot.this_client_player_index.0;
+
+        (1..num_players)
+            .map(|offset| (me + offset) % num_players)
+            .find_map(|index| match &snapshot.players[index] {
+                ClientPlayerView::Teammate { hand, .. } => hand
+                    .iter()
+                    .flatten()
+                    .filter(|slot| !slot.hints.is_touched())
+                    .filter(|slot| snapshot.classify_card(&slot.card) == CardClass::Playable)
+                    .flat_map(|slot| {
+                        [
+                            HintAction::SameFace(slot.card.face),
+                            HintAction::SameSuit(slot.card.suit),
+                        ]
+                    })
+                    .map(|hint| PlayerAction::GiveHint(PlayerIndex(index), hint))
+                    .find(|action| legal_actions.contains(action)),
+                ClientPlayerView::Me { .. } => None,
+            })
+    }
+
+    fn discard_oldest_unclued(snapshot: &GameStateSnapshot) -> Option<PlayerAction> {
+        match snapshot.players.get(snapshot.this_client_player_index.0) {
+            Some(ClientPlayerView::Me { hand, .. }) => hand
+                .iter()
+                .enumerate()
+                .filter_map(|(index, slot)| Some((index, slot.as_ref()?)))
+                .sorted_by_key(|(_, slot)| (slot.hints.is_touched(), slot.draw_number))
+                .next()
+                .map(|(index, _)| PlayerAction::DiscardCard(SlotIndex(index))),
+            _ => None,
+        }
+    }
+}
+
+impl Bot for SimpleBot {
+    fn choose_action(
+        &mut self,
+        snapshot: &GameStateSnapshot,
+        _log: &[GameSnapshotEvent],
+    ) -> PlayerAction {
+        let legal_actions = turn_actions(snapshot);
+
+        Self::known_playable(snapshot)
+            .or_else(|| Self::playable_hint(snapshot, &legal_actions))
+            .or_else(|| Self::discard_oldest_unclued(snapshot))
+            .filter(|action| legal_actions.contains(action))
+            // e.g. discarding isn't allowed with every hint left under strict rules
+            .or_else(|| legal_actions.first().copied())
+            .expect("a player always holds a card on their turn")
+    }
+}
+
+/// Plays a whole game with one bot per seat
+pub fn play_game<R: SeedableRng + Rng>(
+    config: GameConfig,
+    bots: &mut [Box<dyn Bot>],
+) -> Result<GameLog, GameError> {
+    if bots.len() != config.num_players {
+        return Err(GameError::InvalidConfig);
+    }
+
+    let names = (0..config.num_players)
+        .map(|index| format!("bot {}", index + 1))
+        .collect_vec();
+    // the log each seat sees, one event longer after every action
+    let mut logs = vec![vec![]; config.num_players];
+    let mut game_log = GameLog::new::<R>(config)?;
+
+    while !game_log.current_game_state().status.is_finished() {
+        let game_state = game_log.current_game_state();
+        let player = game_state.current_player_index();
+        let snapshot = game_log.into_client_game_state(game_state, player, names.clone());
+
+        let action = bots
+            .get_mut(player.0)
+            .ok_or(GameError::InvalidPlayer(player))?
+            .choose_action(&snapshot, &logs[player.0]);
+        game_log.log(player, action)?;
+
+        for (seat, log) in logs.iter_mut().enumerate() {
+            log.extend(game_log.last_client_event(PlayerIndex(seat), names.clone()));
+        }
+    }
+
+    Ok(game_log)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::model::{CardFace, Hint};
+
+    fn average_score(make_bot: impl Fn(usize) -> Box<dyn Bot>) -> f64 {
+        let seeds = 0..20;
+        let total: usize = seeds
+            .clone()
+            .map(|seed| {
+                let mut bots = (0..3).map(&make_bot).collect_vec();
+                let game_log = play_game::<StdRng>(GameConfig::new(3, seed), &mut bots).unwrap();
+                game_log.current_game_state().played_cards.score()
+            })
+            .sum();
+        total as f64 / seeds.count() as f64
+    }
+
+    #[test]
+    fn test_needs_a_bot_per_seat() {
+        for num_bots in [2, 4] {
+            let mut bots = (0..num_bots)
+                .map(|_| Box::new(SimpleBot::new()) as Box<dyn Bot>)
+                .collect_vec();
+            assert_eq!(
+                play_game::<StdRng>(GameConfig::new(3, 0), &mut bots).unwrap_err(),
+                GameError::InvalidConfig
+            );
+        }
+    }
+
+    #[test]
+    fn test_bots_finish_their_games() {
+        let random = average_score(|seat| Box::new(RandomBot::new(seat as u64)));
+        let simple = average_score(|_| Box::new(SimpleBot::new()));
+
+        assert!(simple > random, "simple {simple} vs random {random}");
+    }
+
+    #[test]
+    fn test_contradicting_hints_are_not_playable() {
+        let game_log = GameLog::new::<StdRng>(GameConfig::new(2, 0)).unwrap();
+        let names = vec!["p1".to_string(), "p2".to_string()];
+        let mut snapshot =
+            game_log.into_client_game_state(game_log.current_game_state(), PlayerIndex(0), names);
+        let ClientPlayerView::Me { hand, .. } = &mut snapshot.players[0] else {
+            unreachable!("the snapshot is the first player's")
+        };
+        let hints = &mut hand[0].as_mut().unwrap().hints;
+        hints.add(Hint::IsFace(CardFace::One));
+        hints.add(Hint::IsFace(CardFace::Two));
+
+        assert_eq!(SimpleBot::known_playable(&snapshot), None);
+    }
+
+    /// Plays at random, writing down the log it was shown on every turn
+    struct LogRecorder {
+        bot: RandomBot,
+        logs: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Bot for LogRecorder {
+        fn choose_action(
+            &mut self,
+            snapshot: &GameStateSnapshot,
+            log: &[GameSnapshotEvent],
+        ) -> PlayerAction {
+            self.logs
+                .borrow_mut()
+                .push(serde_json::to_string(log).unwrap());
+            self.bot.choose_action(snapshot, log)
+        }
+    }
+
+    #[test]
+    fn test_bots_see_the_log_of_their_seat() {
+        let config = GameConfig::new(3, 4);
+        let logs = Rc::new(RefCell::new(vec![]));
+        let mut bots = (0..3)
+            .map(|seat| {
+                Box::new(LogRecorder {
+                    bot: RandomBot::new(seat),
+                    logs: logs.clone(),
+                }) as Box<dyn Bot>
+            })
+            .collect_vec();
+        let played = play_game::<StdRng>(config.clone(), &mut bots).unwrap();
+
+        let names = (1..=3).map(|index| format!("bot {index}")).collect_vec();
+        let mut game_log = GameLog::new::<StdRng>(config).unwrap();
+        for (event, log) in played.log.iter().zip_eq(logs.borrow().iter()) {
+            let player = event.event_player_index;
+            let expected = game_log.into_client_game_log(player, names.clone());
+            assert_eq!(*log, serde_json::to_string(&expected).unwrap());
+            game_log.log(player, event.event_action).unwrap();
+        }
+    }
+}
@@ -0,0 +1,379 @@
+//! The log of a game as it goes to a client. Only the first event comes with the
+//! state it led to, every later event only has what the client can't work out
+//! itself, and the client replays the effects to get a snapshot per event back.
+//!
+//! `HanabiGame::Started` sends its log this way with `#[serde(with = "client_log")]`,
+//! clients still get a `Vec<GameSnapshotEvent>` once it's deserialized.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::logic::game_outcome;
+use crate::model::{
+    Card, ClientPlayerView, GameEffect, GameError, GameSnapshotEvent, GameStateSnapshot,
+    GameStatus, HiddenSlot, HintKnowledge, PlayerAction, PlayerIndex, Slot, SlotIndex,
+};
+
+/// A client log on the wire, see the module documentation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ClientGameLog {
+    first: Option<GameSnapshotEvent>,
+    events: Vec<ClientLogEvent>,
+}
+
+/// An event after the first one, the turn it was played on is the one the previous
+/// snapshot is at
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct ClientLogEvent {
+    event_player_index: PlayerIndex,
+    event_action: PlayerAction,
+    effects: Vec<GameEffect>,
+    /// The card a teammate drew, the client doesn't see the ones they draw themselves
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    drawn_card: Option<Card>,
+}
+
+impl ClientGameLog {
+    pub fn new(events: &[GameSnapshotEvent]) -> Self {
+        ClientGameLog {
+            first: events.first().cloned(),
+            events: events
+                .iter()
+                .skip(1)
+                .map(|event| ClientLogEvent {
+                    event_player_index: event.event_player_index,
+                    event_action: event.event_action,
+                    effects: event.effects.clone(),
+                    drawn_card: drawn_card(event),
+                })
+                .collect(),
+        }
+    }
+
+    /// Every event with the snapshot it led to, as `GameLog::into_client_game_log`
+    /// made them
+    pub fn replay(self) -> Result<Vec<GameSnapshotEvent>, GameError> {
+        let Some(first) = self.first else {
+            return Ok(vec![]);
+        };
+
+        let mut snapshot_events = vec![first];
+        for event in self.events {
+            let snapshot = &snapshot_events.last().unwrap().post_event_game_snapshot;
+            let mut next = snapshot.clone();
+            next.run_client_effects(&event)?;
+            snapshot_events.push(GameSnapshotEvent {
+                current_turn_count: snapshot.num_rounds,
+                current_turn_player_index: snapshot.current_turn_player_index,
+                event_player_index: event.event_player_index,
+                event_action: event.event_action,
+                effects: event.effects,
+                post_event_game_snapshot: next,
+            });
+        }
+        Ok(snapshot_events)
+    }
+}
+
+/// The card a teammate drew in an event, as the snapshot after it shows
+fn drawn_card(event: &GameSnapshotEvent) -> Option<Card> {
+    event.effects.iter().find_map(|effect| match *effect {
+        GameEffect::DrawCard(PlayerIndex(player), SlotIndex(slot)) => {
+            match event.post_event_game_snapshot.players.get(player)? {
+                ClientPlayerView::Teammate { hand, .. } => Some(hand.get(slot)?.as_ref()?.card),
+                ClientPlayerView::Me { .. } => None,
+            }
+        }
+        _ => None,
+    })
+}
+
+pub fn serialize<S: Serializer>(
+    events: &[GameSnapshotEvent],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    ClientGameLog::new(events).serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<GameSnapshotEvent>, D::Error> {
+    ClientGameLog::deserialize(deserializer)?
+        .replay()
+        .map_err(serde::de::Error::custom)
+}
+
+/// Moves the card of a slot to another one, the cards in between shift by one
+fn move_slot<T>(hand: &mut [T], from: usize, to: usize) -> Option<()> {
+    if from.max(to) >= hand.len() {
+        return None;
+    }
+    if from < to {
+        hand[from..=to].rotate_left(1);
+    } else {
+        hand[to..=from].rotate_right(1);
+    }
+    Some(())
+}
+
+impl GameStateSnapshot {
+    /// Runs an event's effects the way `GameState::run_effect` does, on what the
+    /// client sees
+    fn run_client_effects(&mut self, event: &ClientLogEvent) -> Result<(), GameError> {
+        // a play that neither reached the board nor the discards went in the hole
+        let went_in_the_hole = matches!(event.event_action, PlayerAction::PlayCard(_))
+            && !event.effects.iter().any(|effect| {
+                matches!(
+                    effect,
+                    GameEffect::PlaceOnBoard(_) | GameEffect::AddToDiscard(_)
+                )
+            });
+        if went_in_the_hole {
+            self.cards_in_hole += 1;
+        }
+
+        for effect in &event.effects {
+            self.run_client_effect(*effect, event.drawn_card)?;
+
+            self.outcome = game_outcome(
+                &self.game_config,
+                self.status,
+                self.remaining_bomb_count,
+                &self.played_cards,
+                &self.discard_pile,
+            );
+            if let Some(outcome) = self.outcome {
+                self.status = GameStatus::Finished(outcome);
+            }
+        }
+        Ok(())
+    }
+
+    fn run_client_effect(
+        &mut self,
+        effect: GameEffect,
+        drawn_card: Option<Card>,
+    ) -> Result<(), GameError> {
+        let inconsistent = GameError::InconsistentEffect(effect);
+
+        match effect {
+            GameEffect::DrawCard(PlayerIndex(player), SlotIndex(slot)) => {
+                let draw_number = self.cards_out_of_the_draw_pile();
+                self.draw_pile_count = self.draw_pile_count.checked_sub(1).ok_or(inconsistent)?;
+                match self.players.get_mut(player).ok_or(inconsistent)? {
+                    ClientPlayerView::Me { hand, .. } => {
+                        let slot = hand.get_mut(slot).filter(|s| s.is_none());
+                        *slot.ok_or(inconsistent)? = Some(HiddenSlot {
+                            hints: HintKnowledge::new(),
+                            hint_history: vec![],
+                            draw_number,
+                        });
+                    }
+                    ClientPlayerView::Teammate { hand, .. } => {
+                        let slot = hand.get_mut(slot).filter(|s| s.is_none());
+                        *slot.ok_or(inconsistent)? = Some(Slot {
+                            card: drawn_card.ok_or(inconsistent)?,
+                            hints: HintKnowledge::new(),
+                            hint_history: vec![],
+                            draw_number,
+                        });
+                    }
+                }
+            }
+            GameEffect::MarkLastTurn(turn_count) => {
+                self.last_turn = Some(turn_count);
+                self.status = GameStatus::LastRound {
+                    turns_remaining: (turn_count + 1).saturating_sub(self.num_rounds),
+                };
+            }
+            GameEffect::RemoveCard(PlayerIndex(player), SlotIndex(slot)) => {
+                match self.players.get_mut(player).ok_or(inconsistent)? {
+                    ClientPlayerView::Me { hand, .. } => {
+                        *hand.get_mut(slot).ok_or(inconsistent)? = None;
+                    }
+                    ClientPlayerView::Teammate { hand, .. } => {
+                        *hand.get_mut(slot).ok_or(inconsistent)? = None;
+                    }
+                }
+            }
+            GameEffect::AddToDiscard(card) => {
+                self.discard_pile.push(card);
+            }
+            GameEffect::PlaceOnBoard(card) => {
+                self.played_cards.push(card);
+            }
+            GameEffect::HintCard(PlayerIndex(player), SlotIndex(slot), hint, origin) => {
+                let (hints, hint_history) =
+                    match self.players.get_mut(player).ok_or(inconsistent)? {
+                        ClientPlayerView::Me { hand, .. } => {
+                            let slot = hand.get_mut(slot).and_then(|s| s.as_mut());
+                            let slot = slot.ok_or(inconsistent)?;
+                            (&mut slot.hints, &mut slot.hint_history)
+                        }
+                        ClientPlayerView::Teammate { hand, .. } => {
+                            let slot = hand.get_mut(slot).and_then(|s| s.as_mut());
+                            let slot = slot.ok_or(inconsistent)?;
+                            (&mut slot.hints, &mut slot.hint_history)
+                        }
+                    };
+                hints.add(hint);
+                hint_history.push((hint, origin));
+            }
+            GameEffect::DecHint => {
+                self.remaining_hint_count = self
+                    .remaining_hint_count
+                    .checked_sub(1)
+                    .ok_or(inconsistent)?;
+            }
+            GameEffect::IncHint => {
+                self.remaining_hint_count = self
+                    .remaining_hint_count
+                    .saturating_add(1)
+                    .min(self.game_config.clue_economy.max_hints);
+            }
+            GameEffect::IncHalfHint => {
+                if self.has_half_hint {
+                    self.has_half_hint = false;
+                    self.run_client_effect(GameEffect::IncHint, None)?;
+                } else if self.remaining_hint_count < self.game_config.clue_economy.max_hints {
+                    self.has_half_hint = true;
+                }
+            }
+            GameEffect::BurnFuse => {
+                self.remaining_bomb_count = self
+                    .remaining_bomb_count
+                    .checked_sub(1)
+                    .ok_or(inconsistent)?;
+            }
+            GameEffect::NextTurn(_) | GameEffect::LastTurn => {
+                self.num_rounds += 1;
+                self.current_turn_player_index =
+                    PlayerIndex(self.num_rounds as usize % self.players.len());
+                if let GameStatus::LastRound { turns_remaining } = &mut self.status {
+                    *turns_remaining = turns_remaining.saturating_sub(1);
+                }
+            }
+            GameEffect::MoveSlot(PlayerIndex(player), SlotIndex(from), SlotIndex(to)) => {
+                match self.players.get_mut(player).ok_or(inconsistent)? {
+                    ClientPlayerView::Me { hand, .. } => move_slot(hand, from, to),
+                    ClientPlayerView::Teammate { hand, .. } => move_slot(hand, from, to),
+                }
+                .ok_or(inconsistent)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Cards drawn so far, the hidden plays included
+    fn cards_out_of_the_draw_pile(&self) -> usize {
+        let cards_in_hands: usize = self
+            .players
+            .iter()
+            .map(|player| match player {
+                ClientPlayerView::Me { hand, .. } => hand.iter().flatten().count(),
+                ClientPlayerView::Teammate { hand, .. } => hand.iter().flatten().count(),
+            })
+            .sum();
+        self.discard_pile.len() + self.played_cards.score() + self.cards_in_hole + cards_in_hands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::bot::{play_game, Bot, SimpleBot};
+    use crate::client_logic::{GameLog, HanabiGame};
+    use crate::model::{GameConfig, Variant};
+
+    fn play_with_bots(config: GameConfig) -> GameLog {
+        let mut bots = (0..config.num_players)
+            .map(|_| Box::new(SimpleBot::new()) as Box<dyn Bot>)
+            .collect::<Vec<_>>();
+        play_game::<StdRng>(config, &mut bots).unwrap()
+    }
+
+    /// The log each player is sent as the game goes, before and after going through
+    /// the wire
+    fn sent_and_received(game_log: &GameLog) -> Vec<(serde_json::Value, serde_json::Value)> {
+        let num_players = game_log.config.num_players;
+        let names = (0..num_players)
+            .map(|index| format!("p{index}"))
+            .collect::<Vec<_>>();
+        let mut logs = vec![];
+        for event_count in (0..game_log.log.len())
+            .step_by(7)
+            .chain([game_log.log.len()])
+        {
+            let mut partial = game_log.clone();
+            for _ in event_count..game_log.log.len() {
+                partial.undo();
+            }
+            for player in (0..num_players).map(PlayerIndex) {
+                let sent = partial.into_client_game_log(player, names.clone());
+                let message = HanabiGame::Started {
+                    session_id: "1".to_string(),
+                    players: vec![],
+                    game_state: partial.into_client_game_state(
+                        partial.current_game_state(),
+                        player,
+                        names.clone(),
+                    ),
+                    log: sent.clone(),
+                };
+                let json = serde_json::to_string(&message).unwrap();
+                let HanabiGame::Started { log: received, .. } =
+                    serde_json::from_str(&json).unwrap()
+                else {
+                    panic!("a started game was sent");
+                };
+                logs.push((
+                    serde_json::to_value(sent).unwrap(),
+                    serde_json::to_value(received).unwrap(),
+                ));
+            }
+        }
+        logs
+    }
+
+    #[test]
+    fn test_clients_replay_the_log_they_were_sent() {
+        for variant in Variant::iter() {
+            let game_log = play_with_bots(GameConfig::new(3, 4).with_variant(variant));
+            assert!(game_log.current_game_state().status.is_finished());
+            for (sent, received) in sent_and_received(&game_log) {
+                assert_eq!(sent, received, "{variant:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_compact_log_is_smaller() {
+        let game_log = play_with_bots(GameConfig::new(4, 1));
+        let names = (0..4).map(|index| format!("p{index}")).collect::<Vec<_>>();
+        let events = game_log.into_client_game_log(PlayerIndex(0), names);
+
+        let full = serde_json::to_vec(&events).unwrap().len();
+        let compact = serde_json::to_vec(&ClientGameLog::new(&events))
+            .unwrap()
+            .len();
+        assert!(compact * 10 < full, "{compact} bytes against {full}");
+    }
+
+    #[test]
+    fn test_inconsistent_log_is_rejected() {
+        let game_log = play_with_bots(GameConfig::new(2, 0));
+        let names = vec!["p0".to_string(), "p1".to_string()];
+        let events = game_log.into_client_game_log(PlayerIndex(0), names);
+
+        let mut compact = ClientGameLog::new(&events);
+        compact.events[0]
+            .effects
+            .insert(0, GameEffect::RemoveCard(PlayerIndex(0), SlotIndex(9)));
+        assert_eq!(
+            compact.replay().unwrap_err(),
+            GameError::InconsistentEffect(GameEffect::RemoveCard(PlayerIndex(0), SlotIndex(9)))
+        );
+    }
+}
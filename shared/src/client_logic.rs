@@ -4,10 +4,11 @@ use itertools::Itertools;
 use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
+use crate::logic::pace;
 use crate::model::{
-    CardFace, CardSuit, ClientPlayerView, GameConfig, GameEffect, GameOutcome, GameSnapshotEvent,
-    GameState, GameStateSnapshot, HiddenSlot, HintAction, Player, PlayerAction, PlayerIndex,
-    SlotIndex,
+    Card, CardClass, CardFace, CardSuit, ClientPlayerView, GameConfig, GameEffect, GameError,
+    GameOutcome, GameSnapshotEvent, GameState, GameStateSnapshot, HiddenSlot, HintAction,
+    PlayedStacks, Player, PlayerAction, PlayerIndex, SlotIndex, Variant,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -34,6 +35,7 @@ pub enum HanabiGame {
         session_id: String,
         players: Vec<OnlinePlayer>,
         game_state: GameStateSnapshot,
+        #[serde(with = "crate::client_log")]
         log: Vec<GameSnapshotEvent>,
     },
     Ended {
@@ -48,6 +50,8 @@ pub enum HanabiGame {
 pub enum ClientToServerMessage {
     CreateGame {
         player_name: String,
+        #[serde(default)]
+        variant: Variant,
     },
     Join {
         player_name: String,
@@ -69,9 +73,13 @@ struct Lobby {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 
 pub enum ServerToClientMessage {
-    CreatedGame { session_id: String },
+    CreatedGame {
+        session_id: String,
+    },
     UpdatedGameState(HanabiGame),
     Error(String),
+    /// An action of this client was refused by the game
+    GameError(GameError),
 }
 
 #[derive(Debug, Clone)]
@@ -286,22 +294,74 @@ pub struct GameLogEvent {
     pub event_player_index: PlayerIndex,
     pub event_action: PlayerAction,
     pub event_effects: Vec<GameEffect>,
-    pub post_event_game_state: GameState,
 }
 
+/// Events between two stored states, a past state never replays more effects than this
+pub const CHECKPOINT_INTERVAL: usize = 10;
+
+/// Every action of a game and its effects. Only the initial state, a checkpoint every
+/// `CHECKPOINT_INTERVAL` events and the current state are kept, other states are
+/// replayed from the closest checkpoint. Only the initial state and the events
+/// are serialized, the rest is rebuilt when deserializing.
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(try_from = "StoredGameLog")]
 pub struct GameLog {
     pub config: GameConfig,
     pub initial: GameState,
     pub log: Vec<GameLogEvent>,
+    /// The state after each `CHECKPOINT_INTERVAL`th event
+    #[serde(skip_serializing)]
+    checkpoints: Vec<GameState>,
+    #[serde(skip_serializing)]
+    current: GameState,
+}
+
+#[derive(Deserialize)]
+struct StoredGameLog {
+    config: GameConfig,
+    initial: GameState,
+    log: Vec<GameLogEvent>,
+}
+
+impl TryFrom<StoredGameLog> for GameLog {
+    type Error = GameError;
+
+    fn try_from(stored: StoredGameLog) -> Result<Self, Self::Error> {
+        let mut game_log = GameLog {
+            current: stored.initial.clone(),
+            config: stored.config,
+            initial: stored.initial,
+            log: vec![],
+            checkpoints: vec![],
+        };
+        for event in stored.log {
+            game_log.push_event(event)?;
+        }
+        Ok(game_log)
+    }
 }
 
 impl GameLog {
-    pub fn new<R: SeedableRng + Rng>(config: GameConfig) -> Self {
+    pub fn new<R: SeedableRng + Rng>(config: GameConfig) -> Result<Self, GameError> {
+        let initial = GameState::start_with_seed::<R>(&config)?;
+        Ok(GameLog {
+            current: initial.clone(),
+            initial,
+            config,
+            log: vec![],
+            checkpoints: vec![],
+        })
+    }
+
+    /// A log of a game dealt some other way than from its seed
+    #[cfg(test)]
+    pub(crate) fn from_initial(initial: GameState) -> Self {
         GameLog {
-            config: config.clone(),
-            initial: GameState::start_with_seed::<R>(&config).unwrap(),
+            config: initial.config.clone(),
+            current: initial.clone(),
+            initial,
             log: vec![],
+            checkpoints: vec![],
         }
     }
 
@@ -309,82 +369,168 @@ impl GameLog {
         &'a mut self,
         actor: PlayerIndex,
         action: PlayerAction,
-    ) -> Result<&'a GameLogEvent, String> {
-        let mut current_game_state = self.current_game_state();
-        let current_turn_count = current_game_state.turn;
-        let current_turn_player_index = current_game_state.current_player_index();
-
-        let effects = current_game_state.play(action.clone())?;
-        let logged_effects = effects.clone();
-
-        current_game_state.run_effects(effects)?;
-        let new_game_state = current_game_state;
-
-        let new_log_event = GameLogEvent {
-            current_turn_count,
-            current_turn_player_index,
+    ) -> Result<&'a GameLogEvent, GameError> {
+        let current_game_state = &self.current;
+        current_game_state.check_actor(actor, &action)?;
+        let effects = current_game_state.play(action)?;
+
+        self.push_event(GameLogEvent {
+            current_turn_count: current_game_state.turn,
+            current_turn_player_index: current_game_state.current_player_index(),
             event_player_index: actor,
             event_action: action,
-            event_effects: logged_effects,
-            post_event_game_state: new_game_state.clone(),
-        };
+            event_effects: effects,
+        })?;
 
-        self.log.push(new_log_event);
+        Ok(self.log.last().unwrap())
+    }
+
+    /// Runs the effects of an event on the current state, checkpointing when due.
+    /// The state stays as it was when an effect fails.
+    fn push_event(&mut self, event: GameLogEvent) -> Result<(), GameError> {
+        let mut next = self.current.clone();
+        next.run_effects(event.event_effects.clone())?;
+        self.current = next;
+        self.log.push(event);
 
-        Ok(&self.log.last().unwrap())
+        if self.log.len().is_multiple_of(CHECKPOINT_INTERVAL) {
+            self.checkpoints.push(self.current.clone());
+        }
+        Ok(())
     }
 
     pub fn current_game_state(&self) -> GameState {
-        self.log
-            .last()
-            .map(|e| e.post_event_game_state.clone())
-            .unwrap_or(self.initial.clone())
+        self.current.clone()
+    }
+
+    /// The state once the first `event_count` events have been played, 0 being the initial state
+    pub fn state_after(&self, event_count: usize) -> GameState {
+        if event_count >= self.log.len() {
+            self.current_game_state()
+        } else {
+            self.replay(event_count)
+        }
+    }
+
+    /// Replays the events following the closest checkpoint
+    fn replay(&self, event_count: usize) -> GameState {
+        let checkpoint_count = event_count / CHECKPOINT_INTERVAL;
+        let mut game_state = match checkpoint_count {
+            0 => self.initial.clone(),
+            count => self.checkpoints[count - 1].clone(),
+        };
+        for event in &self.log[checkpoint_count * CHECKPOINT_INTERVAL..event_count] {
+            game_state
+                .run_effects(event.event_effects.clone())
+                .expect("logged effects already ran once");
+        }
+        game_state
     }
 
     pub fn undo(&mut self) {
-        self.log.pop();
+        if self.log.pop().is_some() {
+            self.checkpoints
+                .truncate(self.log.len() / CHECKPOINT_INTERVAL);
+            self.current = self.replay(self.log.len());
+        }
+    }
+
+    /// Variants like Throw It In A Hole keep play results hidden until the game is over
+    fn hides_play_results(&self) -> bool {
+        self.config.variant.hides_play_results() && !self.current.status.is_finished()
     }
 
+    /// Whether each card of the discard pile got there by a misplay, in the order
+    /// the cards were discarded
+    fn discarded_by_misplay(&self) -> impl Iterator<Item = bool> + '_ {
+        let initial = iter::repeat_n(false, self.initial.discard_pile.len());
+        initial.chain(self.log.iter().flat_map(|event| {
+            let is_play = matches!(event.event_action, PlayerAction::PlayCard(_));
+            event
+                .event_effects
+                .iter()
+                .filter(|effect| matches!(effect, GameEffect::AddToDiscard(_)))
+                .map(move |_| is_play)
+        }))
+    }
+
+    /// Every event as the client sees it with the snapshot it led to. Only the first
+    /// snapshot goes on the wire, see `client_log`.
     pub fn into_client_game_log(
         &self,
         client_player_index: PlayerIndex,
         name: Vec<String>,
     ) -> Vec<GameSnapshotEvent> {
+        let mut game_state = self.initial.clone();
+
         self.log
             .iter()
-            .map(
-                |GameLogEvent {
-                     current_turn_count,
-                     current_turn_player_index,
-                     event_player_index,
-                     event_action,
-                     event_effects,
-                     post_event_game_state,
-                 }| {
-                    GameSnapshotEvent {
-                        current_turn_count: *current_turn_count,
-                        current_turn_player_index: *current_turn_player_index,
-                        event_player_index: *event_player_index,
-                        event_action: event_action.clone(),
-                        effects: event_effects.clone(),
-                        post_event_game_snapshot: self.into_client_game_state(
-                            post_event_game_state.clone(),
-                            client_player_index,
-                            name.clone(),
-                        ),
-                    }
-                },
-            )
+            .map(|event| {
+                // replaying the whole log once is cheaper than a lookup per event
+                game_state
+                    .run_effects(event.event_effects.clone())
+                    .expect("logged effects already ran once");
+                self.client_event(event, game_state.clone(), client_player_index, name.clone())
+            })
             .collect_vec()
     }
 
+    /// The last event as `into_client_game_log` shows it, without replaying the rest
+    pub fn last_client_event(
+        &self,
+        client_player_index: PlayerIndex,
+        name: Vec<String>,
+    ) -> Option<GameSnapshotEvent> {
+        let event = self.log.last()?;
+        Some(self.client_event(event, self.current_game_state(), client_player_index, name))
+    }
+
+    /// An event as a client sees it, `game_state` being the state it led to
+    fn client_event(
+        &self,
+        event: &GameLogEvent,
+        game_state: GameState,
+        client_player_index: PlayerIndex,
+        name: Vec<String>,
+    ) -> GameSnapshotEvent {
+        let GameLogEvent {
+            current_turn_count,
+            current_turn_player_index,
+            event_player_index,
+            event_action,
+            event_effects,
+        } = event;
+
+        GameSnapshotEvent {
+            current_turn_count: *current_turn_count,
+            current_turn_player_index: *current_turn_player_index,
+            event_player_index: *event_player_index,
+            event_action: *event_action,
+            effects: match event_action {
+                PlayerAction::PlayCard(_) if self.hides_play_results() => event_effects
+                    .iter()
+                    .filter(|e| {
+                        !matches!(e, GameEffect::PlaceOnBoard(_) | GameEffect::AddToDiscard(_))
+                    })
+                    .copied()
+                    .collect(),
+                _ => event_effects.clone(),
+            },
+            post_event_game_snapshot: self.into_client_game_state(
+                game_state,
+                client_player_index,
+                name,
+            ),
+        }
+    }
+
     pub fn into_client_game_state(
         &self,
         game_state: GameState,
         client_player_index: PlayerIndex,
         name: Vec<String>,
     ) -> GameStateSnapshot {
-        GameStateSnapshot {
+        let mut snapshot = GameStateSnapshot {
             this_client_player_index: client_player_index,
             draw_pile_count: game_state.draw_pile.len() as u8,
             played_cards: game_state.played_cards.clone(),
@@ -402,7 +548,8 @@ impl GameLog {
                                 .iter()
                                 .map(|h| {
                                     h.as_ref().map(|s| HiddenSlot {
-                                        hints: s.hints.clone(),
+                                        hints: s.hints,
+                                        hint_history: s.hint_history.clone(),
                                         draw_number: s.draw_number,
                                     })
                                 })
@@ -418,16 +565,55 @@ impl GameLog {
                 .collect(),
             remaining_bomb_count: game_state.remaining_bomb_count,
             remaining_hint_count: game_state.remaining_hint_count,
+            has_half_hint: game_state.has_half_hint,
             current_turn_player_index: game_state.current_player_index(),
             num_rounds: game_state.turn,
             last_turn: game_state.last_turn,
             outcome: game_state.outcome,
+            status: game_state.status,
+            cards_in_hole: 0,
             game_config: self.config.clone(),
+        };
+
+        if self.hides_play_results() {
+            // the discard pile only grows, its cards line up with the log's discards
+            let (misplays, discards): (Vec<_>, Vec<_>) = game_state
+                .discard_pile
+                .iter()
+                .zip(self.discarded_by_misplay())
+                .partition(|(_, is_misplay)| *is_misplay);
+            snapshot.discard_pile = discards.into_iter().map(|(card, _)| *card).collect();
+            snapshot.cards_in_hole = snapshot.played_cards.score() + misplays.len();
+            snapshot.played_cards = PlayedStacks::new();
         }
+
+        snapshot
     }
 }
 
 impl GameStateSnapshot {
+    /// Only counts the cards this client can see, see `GameState::max_achievable_score`
+    pub fn max_achievable_score(&self) -> usize {
+        self.game_config
+            .variant
+            .max_achievable_score(&self.played_cards, &self.discard_pile)
+    }
+
+    pub fn classify_card(&self, card: &Card) -> CardClass {
+        self.game_config
+            .variant
+            .classify_card(&self.played_cards, &self.discard_pile, card)
+    }
+
+    pub fn pace(&self) -> isize {
+        pace(
+            self.played_cards.score(),
+            self.draw_pile_count as usize,
+            self.players.len(),
+            self.max_achievable_score(),
+        )
+    }
+
     pub fn apply_local_mutation(&mut self, action: PlayerAction) {
         match action {
             PlayerAction::MoveSlot(
@@ -451,3 +637,290 @@ impl GameStateSnapshot {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::model::Variant;
+
+    #[test]
+    fn test_log_rejects_out_of_turn_actions() {
+        let mut game_log = GameLog::new::<StdRng>(GameConfig::new(2, 0)).unwrap();
+
+        assert_matches!(
+            game_log.log(PlayerIndex(1), PlayerAction::PlayCard(SlotIndex(0))),
+            Err(GameError::NotYourTurn {
+                current_player: PlayerIndex(0)
+            })
+        );
+        assert_matches!(
+            game_log.log(
+                PlayerIndex(1),
+                PlayerAction::MoveSlot(PlayerIndex(0), SlotIndex(0), SlotIndex(1))
+            ),
+            Err(GameError::NotYourHand)
+        );
+        assert!(game_log.log.is_empty());
+
+        // moving cards in your own hand is fine even when it isn't your turn
+        game_log
+            .log(
+                PlayerIndex(1),
+                PlayerAction::MoveSlot(PlayerIndex(1), SlotIndex(0), SlotIndex(1)),
+            )
+            .unwrap();
+        game_log
+            .log(PlayerIndex(0), PlayerAction::PlayCard(SlotIndex(0)))
+            .unwrap();
+        assert_eq!(game_log.log.len(), 2);
+    }
+
+    #[test]
+    fn test_hole_variant_hides_play_results_until_game_over() {
+        let config = GameConfig::new(2, 0).with_variant(Variant::ThrowItInAHole);
+        let mut game_log = GameLog::new::<StdRng>(config.clone()).unwrap();
+        let names = vec!["p1".to_string(), "p2".to_string()];
+
+        for _ in 0..4 {
+            let player = game_log.current_game_state().current_player_index();
+            game_log
+                .log(player, PlayerAction::PlayCard(SlotIndex(0)))
+                .unwrap();
+        }
+
+        let game_state = game_log.current_game_state();
+        let snapshot =
+            game_log.into_client_game_state(game_state.clone(), PlayerIndex(0), names.clone());
+        assert!(snapshot.played_cards.is_empty());
+        assert!(snapshot.discard_pile.is_empty());
+        assert_eq!(snapshot.cards_in_hole, 4);
+        // the strikes still show
+        assert_eq!(
+            snapshot.remaining_bomb_count,
+            game_state.remaining_bomb_count
+        );
+        assert!(snapshot.remaining_bomb_count < config.num_fuses);
+
+        let client_log = game_log.into_client_game_log(PlayerIndex(1), names.clone());
+        let effects = client_log
+            .iter()
+            .flat_map(|e| e.effects.iter())
+            .collect_vec();
+        assert!(effects
+            .iter()
+            .all(|e| !matches!(e, GameEffect::PlaceOnBoard(_) | GameEffect::AddToDiscard(_))));
+        assert!(effects.contains(&&GameEffect::BurnFuse));
+
+        while !game_log.current_game_state().status.is_finished() {
+            let player = game_log.current_game_state().current_player_index();
+            game_log
+                .log(player, PlayerAction::PlayCard(SlotIndex(0)))
+                .unwrap();
+        }
+
+        let game_state = game_log.current_game_state();
+        let snapshot = game_log.into_client_game_state(game_state.clone(), PlayerIndex(0), names);
+        assert_eq!(snapshot.played_cards, game_state.played_cards);
+        assert_eq!(snapshot.discard_pile, game_state.discard_pile);
+        assert_eq!(snapshot.cards_in_hole, 0);
+    }
+
+    #[test]
+    fn test_hole_variant_hides_played_cards() {
+        let config = GameConfig::new(2, 0).with_variant(Variant::ThrowItInAHole);
+        let names = vec!["p1".to_string(), "p2".to_string()];
+
+        // the first player plays a card they know nothing about
+        let played_by_first_player = |card: Card| {
+            let mut initial = GameState::start_with_seed::<StdRng>(&config).unwrap();
+            initial.players[0].hand[0].as_mut().unwrap().card = card;
+            let mut game_log = GameLog {
+                config: config.clone(),
+                current: initial.clone(),
+                initial,
+                log: vec![],
+                checkpoints: vec![],
+            };
+            game_log
+                .log(PlayerIndex(0), PlayerAction::PlayCard(SlotIndex(0)))
+                .unwrap();
+            (0..2)
+                .map(|player| {
+                    let event = game_log.last_client_event(PlayerIndex(player), names.clone());
+                    serde_json::to_value(event).unwrap()
+                })
+                .collect_vec()
+        };
+
+        // which card went in the hole doesn't show, only whether it burned a fuse
+        let red_one = played_by_first_player(Card {
+            face: CardFace::One,
+            suit: CardSuit::Red,
+        });
+        let blue_one = played_by_first_player(Card {
+            face: CardFace::One,
+            suit: CardSuit::Blue,
+        });
+        assert_eq!(red_one, blue_one);
+
+        let red_three = played_by_first_player(Card {
+            face: CardFace::Three,
+            suit: CardSuit::Red,
+        });
+        let blue_four = played_by_first_player(Card {
+            face: CardFace::Four,
+            suit: CardSuit::Blue,
+        });
+        assert_eq!(red_three, blue_four);
+        assert_ne!(red_one, red_three);
+    }
+
+    #[test]
+    fn test_hole_variant_keeps_the_discards_in_order() {
+        let config = GameConfig::new(2, 0).with_variant(Variant::ThrowItInAHole);
+        let red_three = Card {
+            face: CardFace::Three,
+            suit: CardSuit::Red,
+        };
+        let blue_two = Card {
+            face: CardFace::Two,
+            suit: CardSuit::Blue,
+        };
+        let mut initial = GameState::start_with_seed::<StdRng>(&config).unwrap();
+        initial.players[0].hand[0].as_mut().unwrap().card = red_three;
+        initial.players[0].hand[1].as_mut().unwrap().card = red_three;
+        initial.players[1].hand[0].as_mut().unwrap().card = blue_two;
+        let mut game_log = GameLog {
+            config,
+            current: initial.clone(),
+            initial,
+            log: vec![],
+            checkpoints: vec![],
+        };
+
+        // a red three misplayed, then a blue two and the other red three discarded
+        for (player, action) in [
+            (0, PlayerAction::PlayCard(SlotIndex(0))),
+            (1, PlayerAction::DiscardCard(SlotIndex(0))),
+            (0, PlayerAction::DiscardCard(SlotIndex(1))),
+        ] {
+            game_log.log(PlayerIndex(player), action).unwrap();
+        }
+
+        let names = vec!["p1".to_string(), "p2".to_string()];
+        let snapshot =
+            game_log.into_client_game_state(game_log.current_game_state(), PlayerIndex(1), names);
+        assert_eq!(snapshot.discard_pile, vec![blue_two, red_three]);
+        assert_eq!(snapshot.cards_in_hole, 1);
+    }
+
+    /// Discards, with a hint on every third turn, until the game is over
+    fn play_to_the_end(game_log: &mut GameLog) -> Vec<GameState> {
+        let mut game_states = vec![game_log.current_game_state()];
+        while !game_log.current_game_state().status.is_finished() {
+            let game_state = game_log.current_game_state();
+            let player = game_state.current_player_index();
+            let teammate = PlayerIndex((player.0 + 1) % game_state.players.len());
+            let action = match &game_state.players[teammate.0].hand[0] {
+                Some(slot)
+                    if game_state.turn.is_multiple_of(3) && game_state.remaining_hint_count > 0 =>
+                {
+                    PlayerAction::GiveHint(teammate, HintAction::SameFace(slot.card.face))
+                }
+                _ => PlayerAction::DiscardCard(SlotIndex(0)),
+            };
+            game_log.log(player, action).unwrap();
+            game_states.push(game_log.current_game_state());
+        }
+        game_states
+    }
+
+    #[test]
+    fn test_log_rebuilds_past_states() {
+        let mut game_log = GameLog::new::<StdRng>(GameConfig::new(3, 7)).unwrap();
+        let game_states = play_to_the_end(&mut game_log);
+        assert!(game_log.log.len() > 3 * CHECKPOINT_INTERVAL);
+
+        for (event_count, game_state) in game_states.iter().enumerate() {
+            assert_eq!(&game_log.state_after(event_count), game_state);
+        }
+
+        // only the initial state and the events go on the wire
+        let serialized = serde_json::to_string(&game_log).unwrap();
+        let deserialized: GameLog = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.checkpoints, game_log.checkpoints);
+        assert_eq!(
+            deserialized.current_game_state(),
+            game_log.current_game_state()
+        );
+
+        let event_count = game_log.log.len();
+        for _ in 0..CHECKPOINT_INTERVAL + 1 {
+            game_log.undo();
+        }
+        let event_count = event_count - CHECKPOINT_INTERVAL - 1;
+        assert_eq!(game_log.current_game_state(), game_states[event_count]);
+        assert_eq!(
+            game_log.checkpoints.len(),
+            event_count / CHECKPOINT_INTERVAL
+        );
+    }
+
+    #[test]
+    fn test_rejected_event_leaves_the_state_as_it_was() {
+        let mut game_log = GameLog::new::<StdRng>(GameConfig::new(2, 0)).unwrap();
+        game_log
+            .log(PlayerIndex(0), PlayerAction::PlayCard(SlotIndex(0)))
+            .unwrap();
+        let game_state = game_log.current_game_state();
+
+        // the hint goes before the slot turns out not to exist
+        let event = GameLogEvent {
+            current_turn_count: game_state.turn,
+            current_turn_player_index: PlayerIndex(1),
+            event_player_index: PlayerIndex(1),
+            event_action: PlayerAction::DiscardCard(SlotIndex(9)),
+            event_effects: vec![
+                GameEffect::DecHint,
+                GameEffect::RemoveCard(PlayerIndex(1), SlotIndex(9)),
+            ],
+        };
+        assert!(game_log.push_event(event).is_err());
+
+        assert_eq!(game_log.log.len(), 1);
+        assert_eq!(game_log.current_game_state(), game_state);
+        assert_eq!(
+            game_log.current_game_state(),
+            game_log.replay(game_log.log.len())
+        );
+    }
+
+    #[test]
+    fn test_create_game_defaults_to_the_standard_variant() {
+        let message = ClientToServerMessage::CreateGame {
+            player_name: "p0".to_string(),
+            variant: Variant::Rainbow,
+        };
+        let serialized = serde_json::to_string(&message).unwrap();
+        assert!(matches!(
+            serde_json::from_str(&serialized).unwrap(),
+            ClientToServerMessage::CreateGame {
+                variant: Variant::Rainbow,
+                ..
+            }
+        ));
+
+        // sent by clients that predate variants
+        let old_message = r#"{"CreateGame":{"player_name":"p0"}}"#;
+        assert!(matches!(
+            serde_json::from_str(old_message).unwrap(),
+            ClientToServerMessage::CreateGame {
+                variant: Variant::Standard,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,646 @@
+//! A bot following a small subset of the usual cooperative conventions, the ones
+//! every table agrees on:
+//!
+//! - **Chop**: a player's chop is their oldest card nobody hinted, the card they
+//!   discard when they have nothing better to do.
+//! - **Focus**: a hint is about one card. That's the chop if the hint touches it,
+//!   otherwise the newest card it touches for the first time, otherwise (a hint
+//!   touching only hinted cards) the newest card it touches.
+//! - **Play clues**: a hint whose focus isn't the chop says the focused card can be
+//!   played right now. The receiver keeps the cards it can be that were playable
+//!   when the hint was given, and plays it once all of those still are.
+//! - **Save clues**: a hint on the chop saves it if the card could be worth keeping:
+//!   critical, or a two hinted by its face. A saved card is never discarded and gets
+//!   played once its hints prove it playable. Nothing else on the chop makes it a
+//!   play clue.
+//! - **5 saves and 2 saves**: a five or a two about to be discarded is saved with
+//!   a face hint, other critical cards with a suit hint if it reads as a save.
+//!   A two isn't saved when another player holds the other copy.
+//!
+//! Hints are read only from what every player knows (the board, the discard pile
+//! and the hints themselves), so a hint giver reads its hint the way the receiver
+//! will before giving it. Finesses are neither given nor read.
+
+use std::collections::HashMap;
+
+use crate::bot::Bot;
+use crate::model::{
+    Card, CardClass, CardFace, CardKnowledge, ClientPlayerView, GameSnapshotEvent,
+    GameStateSnapshot, HintAction, HintKnowledge, PlayerAction, PlayerIndex, SlotIndex,
+};
+
+/// What a hint told the player holding its focused card
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Clue {
+    /// Playable now, as one of these cards
+    Play(Vec<Card>),
+    Save,
+}
+
+/// A slot of any hand, the cards of teammates are known
+#[derive(Debug, Clone)]
+struct SlotView {
+    index: SlotIndex,
+    draw_number: usize,
+    hints: HintKnowledge,
+    card: Option<Card>,
+}
+
+fn slot_views(player: &ClientPlayerView) -> Vec<SlotView> {
+    match player {
+        ClientPlayerView::Me { hand, .. } => hand
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let slot = slot.as_ref()?;
+                Some(SlotView {
+                    index: SlotIndex(index),
+                    draw_number: slot.draw_number,
+                    hints: slot.hints,
+                    card: None,
+                })
+            })
+            .collect(),
+        ClientPlayerView::Teammate { hand, .. } => hand
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let slot = slot.as_ref()?;
+                Some(SlotView {
+                    index: SlotIndex(index),
+                    draw_number: slot.draw_number,
+                    hints: slot.hints,
+                    card: Some(slot.card),
+                })
+            })
+            .collect(),
+    }
+}
+
+/// The oldest card nobody hinted
+fn chop(hand: &[SlotView]) -> Option<&SlotView> {
+    hand.iter()
+        .filter(|slot| !slot.hints.is_touched())
+        .min_by_key(|slot| slot.draw_number)
+}
+
+fn is_named_by(hints: &HintKnowledge, hint: &HintAction) -> bool {
+    match hint {
+        HintAction::SameSuit(suit) => hints.suit_hints().any(|s| s == *suit),
+        HintAction::SameFace(face) => hints.face_hints().any(|f| f == *face),
+    }
+}
+
+/// The hand of a teammate once they receive a hint
+fn hinted_hand(hand: &[SlotView], hint: &HintAction) -> Vec<SlotView> {
+    hand.iter()
+        .map(|slot| {
+            let mut hinted = slot.clone();
+            if let Some(card) = slot.card {
+                hinted.hints.add(hint.hint(hint.touches(&card)));
+            }
+            hinted
+        })
+        .collect()
+}
+
+/// Reads a hint the way every player does, from the hands right before and right
+/// after it. Gives the draw number of the focused card and what it was told.
+fn read_clue(
+    snapshot: &GameStateSnapshot,
+    hint: &HintAction,
+    before: &[SlotView],
+    after: &[SlotView],
+) -> Option<(usize, Clue)> {
+    let touched = after
+        .iter()
+        .filter(|slot| is_named_by(&slot.hints, hint))
+        .collect::<Vec<_>>();
+    let was_touched = |slot: &SlotView| {
+        before
+            .iter()
+            .any(|b| b.draw_number == slot.draw_number && b.hints.is_touched())
+    };
+    let chop = chop(before).map(|slot| slot.draw_number);
+
+    let new = touched.iter().filter(|slot| !was_touched(slot));
+    let focus = new
+        .clone()
+        .find(|slot| Some(slot.draw_number) == chop)
+        .or_else(|| new.max_by_key(|slot| slot.draw_number))
+        .or_else(|| touched.iter().max_by_key(|slot| slot.draw_number))?;
+
+    let public_cards = snapshot
+        .played_cards
+        .cards()
+        .chain(snapshot.discard_pile.iter().copied())
+        .collect::<Vec<_>>();
+    let candidates = CardKnowledge::new(snapshot.game_config.variant, &focus.hints, &public_cards)
+        .possible_cards()
+        .map(|card| (card, snapshot.classify_card(&card)))
+        .collect::<Vec<_>>();
+
+    let could_be_saved = candidates.iter().any(|(card, class)| match class {
+        CardClass::Critical => true,
+        CardClass::Normal => {
+            card.face == CardFace::Two && *hint == HintAction::SameFace(CardFace::Two)
+        }
+        _ => false,
+    });
+    let playable = candidates
+        .iter()
+        .filter(|(_, class)| *class == CardClass::Playable)
+        .map(|(card, _)| *card)
+        .collect::<Vec<_>>();
+
+    let clue = if (Some(focus.draw_number) == chop && could_be_saved) || playable.is_empty() {
+        Clue::Save
+    } else {
+        Clue::Play(playable)
+    };
+    Some((focus.draw_number, clue))
+}
+
+/// Plays a seat following the conventions described at the top of this module
+#[derive(Default)]
+pub struct ConventionBot;
+
+impl ConventionBot {
+    pub fn new() -> Self {
+        ConventionBot
+    }
+}
+
+/// Everything one turn's decision looks at
+struct Turn<'a> {
+    snapshot: &'a GameStateSnapshot,
+    legal_actions: Vec<PlayerAction>,
+    /// What each hinted card was told last, by draw number
+    clues: HashMap<usize, Clue>,
+}
+
+impl<'a> Turn<'a> {
+    fn new(snapshot: &'a GameStateSnapshot, log: &[GameSnapshotEvent]) -> Self {
+        let mut clues = HashMap::new();
+        let mut before: Option<&GameStateSnapshot> = None;
+
+        for event in log {
+            let after = &event.post_event_game_snapshot;
+            if let PlayerAction::GiveHint(PlayerIndex(target), hint) = &event.event_action {
+                let hand_after = after
+                    .players
+                    .get(*target)
+                    .map(slot_views)
+                    .unwrap_or_default();
+                let hand_before = match before {
+                    Some(before) => before
+                        .players
+                        .get(*target)
+                        .map(slot_views)
+                        .unwrap_or_default(),
+                    // nothing was hinted before the first event
+                    None => hand_after
+                        .iter()
+                        .map(|slot| SlotView {
+                            hints: HintKnowledge::new(),
+                            ..slot.clone()
+                        })
+                        .collect(),
+                };
+                if let Some((draw_number, clue)) = read_clue(after, hint, &hand_before, &hand_after)
+                {
+                    clues.insert(draw_number, clue);
+                }
+            }
+            before = Some(after);
+        }
+
+        Turn {
+            snapshot,
+            legal_actions: snapshot
+                .legal_actions()
+                .into_iter()
+                .filter(|action| !matches!(action, PlayerAction::MoveSlot(_, _, _)))
+                .collect(),
+            clues,
+        }
+    }
+
+    fn me(&self) -> PlayerIndex {
+        self.snapshot.this_client_player_index
+    }
+
+    fn my_hand(&self) -> Vec<SlotView> {
+        self.snapshot
+            .players
+            .get(self.me().0)
+            .map(slot_views)
+            .unwrap_or_default()
+    }
+
+    /// Teammates in the order they play after this player
+    fn teammates(&self) -> impl Iterator<Item = (PlayerIndex, Vec<SlotView>)> + '_ {
+        let num_players = self.snapshot.players.len();
+        (1..num_players).map(move |offset| {
+            let index = (self.me().0 + offset) % num_players;
+            (
+                PlayerIndex(index),
+                slot_views(&self.snapshot.players[index]),
+            )
+        })
+    }
+
+    fn legal(&self, action: PlayerAction) -> Option<PlayerAction> {
+        self.legal_actions.contains(&action).then_some(action)
+    }
+
+    fn class(&self, card: &Card) -> CardClass {
+        self.snapshot.classify_card(card)
+    }
+
+    /// The cards a slot of this player's hand can be, narrowed down by a play clue
+    fn my_candidates(&self, slot: &SlotView, knowledge: &CardKnowledge) -> Vec<Card> {
+        knowledge
+            .possible_cards()
+            .filter(|card| match self.clues.get(&slot.draw_number) {
+                Some(Clue::Play(promised)) => promised.contains(card),
+                _ => true,
+            })
+            .collect()
+    }
+
+    fn my_slots_where(&self, class: CardClass) -> Vec<SlotView> {
+        let knowledge = self.snapshot.hand_knowledge();
+        self.my_hand()
+            .into_iter()
+            .filter(|slot| {
+                let Some(Some(knowledge)) = knowledge.get(slot.index.0) else {
+                    return false;
+                };
+                let candidates = self.my_candidates(slot, knowledge);
+                !candidates.is_empty() && candidates.iter().all(|card| self.class(card) == class)
+            })
+            .collect()
+    }
+
+    fn play(&self) -> Option<PlayerAction> {
+        self.my_slots_where(CardClass::Playable)
+            .into_iter()
+            .min_by_key(|slot| slot.draw_number)
+            .and_then(|slot| self.legal(PlayerAction::PlayCard(slot.index)))
+    }
+
+    /// Cards hinted in a teammate's hand, which nobody needs another hint about
+    fn is_hinted_elsewhere(&self, card: &Card) -> bool {
+        self.teammates().any(|(_, hand)| {
+            hand.iter()
+                .any(|slot| slot.hints.is_touched() && slot.card == Some(*card))
+        })
+    }
+
+    /// Saves the chop of the next player when they have nothing to play
+    fn save(&self) -> Option<PlayerAction> {
+        let (next, hand) = self.teammates().next()?;
+        let chop = chop(&hand)?;
+        let card = chop.card?;
+
+        let has_play = hand
+            .iter()
+            .any(|slot| matches!(self.clues.get(&slot.draw_number), Some(Clue::Play(_))));
+        let other_copy_held = self.teammates().any(|(_, hand)| {
+            hand.iter()
+                .any(|slot| slot.card == Some(card) && slot.draw_number != chop.draw_number)
+        });
+        let worth_saving = match self.class(&card) {
+            CardClass::Critical => true,
+            CardClass::Normal => card.face == CardFace::Two && !other_copy_held,
+            _ => false,
+        };
+        if has_play || !worth_saving {
+            return None;
+        }
+
+        let hints = match card.face {
+            CardFace::Five | CardFace::Two => vec![HintAction::SameFace(card.face)],
+            _ => vec![
+                HintAction::SameSuit(card.suit),
+                HintAction::SameFace(card.face),
+            ],
+        };
+        hints
+            .into_iter()
+            .filter(|hint| {
+                let after = hinted_hand(&hand, hint);
+                read_clue(self.snapshot, hint, &hand, &after)
+                    == Some((chop.draw_number, Clue::Save))
+            })
+            .find_map(|hint| self.legal(PlayerAction::GiveHint(next, hint)))
+    }
+
+    /// How good a hint is, if every card it makes someone play is really playable
+    fn rate_hint(&self, hand: &[SlotView], hint: &HintAction) -> Option<isize> {
+        let after = hinted_hand(hand, hint);
+        let (focus, clue) = read_clue(self.snapshot, hint, hand, &after)?;
+        let focus_card = hand
+            .iter()
+            .find(|slot| slot.draw_number == focus)
+            .and_then(|slot| slot.card)?;
+
+        match clue {
+            Clue::Play(promised)
+                if promised.contains(&focus_card)
+                    && self.class(&focus_card) == CardClass::Playable
+                    && !self.is_hinted_elsewhere(&focus_card) => {}
+            Clue::Play(_) => return None,
+            Clue::Save => return Some(0),
+        }
+
+        let newly_touched = hand
+            .iter()
+            .filter(|slot| !slot.hints.is_touched())
+            .filter_map(|slot| slot.card)
+            .filter(|card| hint.touches(card));
+        let rating = newly_touched
+            .map(|card| match self.class(&card) {
+                CardClass::Playable if !self.is_hinted_elsewhere(&card) => 2,
+                CardClass::Trash => -1,
+                _ => 0,
+            })
+            .sum::<isize>();
+        // telling a hinted card it's playable is worth a play too
+        Some(rating.max(2))
+    }
+
+    /// The best play clue, to the next teammate when it's a tie
+    fn play_clue(&self) -> Option<PlayerAction> {
+        self.rated_hints()
+            .filter(|(_, rating)| *rating > 0)
+            .fold(
+                None,
+                |best: Option<(PlayerAction, isize)>, (action, rating)| match best {
+                    Some((_, best_rating)) if best_rating >= rating => best,
+                    _ => Some((action, rating)),
+                },
+            )
+            .map(|(action, _)| action)
+    }
+
+    /// A hint nobody can misread as a play, better than discarding with every hint
+    /// left, touching fives first
+    fn stall_clue(&self) -> Option<PlayerAction> {
+        self.rated_hints()
+            .filter(|(_, rating)| *rating >= 0)
+            .max_by_key(|(action, _)| {
+                matches!(
+                    action,
+                    PlayerAction::GiveHint(_, HintAction::SameFace(CardFace::Five))
+                )
+            })
+            .map(|(action, _)| action)
+    }
+
+    fn rated_hints(&self) -> impl Iterator<Item = (PlayerAction, isize)> + '_ {
+        self.teammates().flat_map(move |(teammate, hand)| {
+            self.legal_actions
+                .iter()
+                .filter_map(|action| match action {
+                    PlayerAction::GiveHint(to, hint) if *to == teammate => {
+                        Some((*action, self.rate_hint(&hand, hint)?))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// Known trash first, then the chop, then the oldest card not waiting to be played
+    fn discard(&self) -> Option<PlayerAction> {
+        let hand = self.my_hand();
+        let not_playing = hand
+            .iter()
+            .filter(|slot| !matches!(self.clues.get(&slot.draw_number), Some(Clue::Play(_))))
+            .min_by_key(|slot| slot.draw_number);
+
+        self.my_slots_where(CardClass::Trash)
+            .first()
+            .or(chop(&hand))
+            .or(not_playing)
+            .and_then(|slot| self.legal(PlayerAction::DiscardCard(slot.index)))
+    }
+
+    fn choose(&self) -> PlayerAction {
+        let at_max_hints =
+            self.snapshot.remaining_hint_count >= self.snapshot.game_config.clue_economy.max_hints;
+
+        self.save()
+            .or_else(|| self.play())
+            .or_else(|| self.play_clue())
+            .or_else(|| at_max_hints.then(|| self.stall_clue()).flatten())
+            .or_else(|| self.discard())
+            .or_else(|| self.legal_actions.first().copied())
+            .expect("a player always holds a card on their turn")
+    }
+}
+
+impl Bot for ConventionBot {
+    fn choose_action(
+        &mut self,
+        snapshot: &GameStateSnapshot,
+        log: &[GameSnapshotEvent],
+    ) -> PlayerAction {
+        Turn::new(snapshot, log).choose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::bot::{play_game, SimpleBot};
+    use crate::client_logic::GameLog;
+    use crate::model::{CardSuit, GameConfig, GameState};
+    use CardFace::*;
+    use CardSuit::*;
+
+    fn card(face: CardFace, suit: CardSuit) -> Card {
+        Card { face, suit }
+    }
+
+    /// A game where each player holds the given cards, oldest first, so the first
+    /// one is their chop
+    fn deal(hands: &[[Card; 5]]) -> GameLog {
+        let config = GameConfig::new(hands.len(), 0);
+        let mut game_state = GameState::start_with_seed::<StdRng>(&config).unwrap();
+        for (player, hand) in game_state.players.iter_mut().zip(hands) {
+            for (slot, card) in player.hand.iter_mut().zip(hand) {
+                slot.as_mut().unwrap().card = *card;
+            }
+        }
+        GameLog::from_initial(game_state)
+    }
+
+    /// What the bot whose turn it is does
+    fn bot_action(game_log: &GameLog) -> PlayerAction {
+        let game_state = game_log.current_game_state();
+        let player = game_state.current_player_index();
+        let names = (0..game_state.players.len())
+            .map(|index| format!("p{index}"))
+            .collect::<Vec<_>>();
+        let snapshot = game_log.into_client_game_state(game_state, player, names.clone());
+        let log = game_log.into_client_game_log(player, names);
+        ConventionBot::new().choose_action(&snapshot, &log)
+    }
+
+    /// Nothing to play, save or give a play clue about
+    fn quiet_hand() -> [Card; 5] {
+        [
+            card(Four, Green),
+            card(Four, White),
+            card(Three, Red),
+            card(Three, Blue),
+            card(Three, Yellow),
+        ]
+    }
+
+    #[test]
+    fn test_saves_a_five_on_the_chop() {
+        let game_log = deal(&[
+            quiet_hand(),
+            [
+                card(Five, Yellow),
+                card(Three, Green),
+                card(Four, Red),
+                card(Three, White),
+                card(Four, Blue),
+            ],
+        ]);
+        assert_eq!(
+            bot_action(&game_log),
+            PlayerAction::GiveHint(PlayerIndex(1), HintAction::SameFace(Five))
+        );
+    }
+
+    #[test]
+    fn test_saves_a_two_nobody_else_holds() {
+        let next_hand = [
+            card(Two, Green),
+            card(Three, Green),
+            card(Four, Red),
+            card(Three, White),
+            card(Four, Blue),
+        ];
+        let save = PlayerAction::GiveHint(PlayerIndex(1), HintAction::SameFace(Two));
+
+        let game_log = deal(&[quiet_hand(), next_hand, quiet_hand()]);
+        assert_eq!(bot_action(&game_log), save);
+
+        // the last player holds the other green two
+        let mut other_copy = quiet_hand();
+        other_copy[4] = card(Two, Green);
+        let game_log = deal(&[quiet_hand(), next_hand, other_copy]);
+        assert_ne!(bot_action(&game_log), save);
+    }
+
+    #[test]
+    fn test_play_clue_keeps_off_the_chop() {
+        // blue would focus the chop and read as a save, the one can only be told
+        // apart by its face
+        let game_log = deal(&[
+            quiet_hand(),
+            [
+                card(Four, Blue),
+                card(Three, Green),
+                card(Four, Yellow),
+                card(One, Blue),
+                card(Three, White),
+            ],
+        ]);
+        assert_eq!(
+            bot_action(&game_log),
+            PlayerAction::GiveHint(PlayerIndex(1), HintAction::SameFace(One))
+        );
+    }
+
+    #[test]
+    fn test_reads_a_single_card_clue_as_a_play() {
+        let mut game_log = deal(&[
+            quiet_hand(),
+            [
+                card(Three, Red),
+                card(Four, Green),
+                card(One, Blue),
+                card(Three, White),
+                card(Four, Yellow),
+            ],
+        ]);
+        game_log
+            .log(
+                PlayerIndex(0),
+                PlayerAction::GiveHint(PlayerIndex(1), HintAction::SameSuit(Blue)),
+            )
+            .unwrap();
+        assert_eq!(bot_action(&game_log), PlayerAction::PlayCard(SlotIndex(2)));
+    }
+
+    #[test]
+    fn test_discards_the_oldest_unhinted_card() {
+        let mut first_hand = quiet_hand();
+        first_hand[0] = card(Five, Red);
+        let mut game_log = deal(&[
+            first_hand,
+            [
+                card(Three, Green),
+                card(Four, White),
+                card(Three, Yellow),
+                card(Four, Blue),
+                card(Three, White),
+            ],
+        ]);
+        for (player, hint) in [
+            (
+                PlayerIndex(0),
+                PlayerAction::GiveHint(PlayerIndex(1), HintAction::SameFace(Three)),
+            ),
+            (
+                PlayerIndex(1),
+                PlayerAction::GiveHint(PlayerIndex(0), HintAction::SameFace(Five)),
+            ),
+        ] {
+            game_log.log(player, hint).unwrap();
+        }
+
+        // the saved five isn't the chop anymore
+        assert_eq!(
+            bot_action(&game_log),
+            PlayerAction::DiscardCard(SlotIndex(1))
+        );
+    }
+
+    fn average_score(num_players: usize, make_bot: impl Fn() -> Box<dyn Bot>) -> f64 {
+        let seeds = 0..20;
+        let total: usize = seeds
+            .clone()
+            .map(|seed| {
+                let mut bots = (0..num_players).map(|_| make_bot()).collect::<Vec<_>>();
+                let config = GameConfig::new(num_players, seed);
+                let game_log = play_game::<StdRng>(config, &mut bots).unwrap();
+                game_log.current_game_state().played_cards.score()
+            })
+            .sum();
+        total as f64 / seeds.count() as f64
+    }
+
+    #[test]
+    fn test_beats_the_simple_bot() {
+        for num_players in [2, 3, 4] {
+            let simple = average_score(num_players, || Box::new(SimpleBot::new()));
+            let conventions = average_score(num_players, || Box::new(ConventionBot::new()));
+
+            assert!(
+                conventions > simple + 3.0,
+                "{num_players} players: conventions {conventions} vs simple {simple}"
+            );
+        }
+    }
+}
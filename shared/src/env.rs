@@ -0,0 +1,543 @@
+//! A game as a reinforcement learning environment: `reset` deals a seed, `step`
+//! plays an action by its index and tells the reward, everything the player to act
+//! knows comes as a vector of numbers.
+//!
+//! Every player plays through the same environment, observations are always taken
+//! by the player whose turn it is. Seats are counted from that player: the player
+//! themselves is seat 0, the next player to act seat 1, and so on.
+//!
+//! # Observation
+//!
+//! Every value is 0 or 1. Cards are numbered `suit * 6 + face` over every suit and
+//! face in the order `CardSuit` and `CardFace` list them, whether or not the
+//! variant deals them, 42 cards in all. The start card of Up or Down is the last
+//! face, no hint names it. With `P` players, hands of `H` cards, `M` hints at most,
+//! `F` fuses and a deck of `D` cards, the vector holds in order:
+//!
+//! 1. Hands, `(P - 1) * H * 42`: the card in each slot of the teammates, seat 1
+//!    first, 1 for the card in the slot. Empty slots are all 0.
+//! 2. Hints, `P * H * 43`: for each slot of each seat, seat 0 included, the cards
+//!    its hints still allow then whether a hint touched it. Empty slots are all 0.
+//! 3. Board, `42`: the cards played.
+//! 4. Discards, `42 * 3`: for each card, one value per copy discarded.
+//! 5. Tokens, `M + 1 + F + D`: one value per hint left, whether half a hint is
+//!    left, one value per fuse left, one value per card in the draw pile.
+//! 6. Last action, `P + 4 + H + P + 7 + 5 + 42 + 1`, all 0 before the first one:
+//!    the seat that acted, whether it was a play, a discard, a suit hint or a face
+//!    hint, the slot played or discarded, the seat hinted, the suit or face hinted,
+//!    the card played or discarded when it's shown, then whether it burned a fuse.
+//!
+//! # Actions
+//!
+//! `H` plays, one per slot, then `H` discards, then for each teammate from seat 1
+//! on, a hint of each of the 7 suits and then each of the 5 numbered faces. Slot
+//! moves aren't part of it, they don't change the game.
+//!
+//! # Reward
+//!
+//! The points an action scores, so a game's rewards add up to its score. Burning
+//! the last fuse takes every point back instead: the rewards of a game that bombs
+//! out add up to 0, even though the engine keeps its score.
+
+use std::fmt;
+
+use enum_map::Enum;
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use strum::IntoEnumIterator;
+
+use crate::client_logic::GameLog;
+use crate::model::{
+    Card, CardFace, CardSuit, ClientPlayerView, GameConfig, GameEffect, GameError,
+    GameSnapshotEvent, GameStateSnapshot, HintAction, PlayerAction, PlayerIndex, SlotIndex,
+};
+
+const SUIT_COUNT: usize = <CardSuit as Enum>::LENGTH;
+const FACE_COUNT: usize = <CardFace as Enum>::LENGTH;
+const CARD_COUNT: usize = SUIT_COUNT * FACE_COUNT;
+/// Faces a hint can name, all but the start card which comes last
+const HINT_FACE_COUNT: usize = FACE_COUNT - 1;
+/// Hints a teammate can be given
+const HINT_COUNT: usize = SUIT_COUNT + HINT_FACE_COUNT;
+/// Copies of a card in a deck at most
+const MAX_COPIES: usize = 3;
+
+fn card_index(card: &Card) -> usize {
+    card.suit.into_usize() * FACE_COUNT + card.face.into_usize()
+}
+
+fn all_cards() -> impl Iterator<Item = Card> {
+    CardSuit::iter().flat_map(|suit| CardFace::iter().map(move |face| Card { face, suit }))
+}
+
+/// Sizes of the observation and the actions of a game config, see the module
+/// documentation for the layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Encoding {
+    players: usize,
+    hand_size: usize,
+    max_hints: usize,
+    fuses: usize,
+    deck_size: usize,
+}
+
+impl Encoding {
+    pub fn new(config: &GameConfig) -> Self {
+        Encoding {
+            players: config.num_players,
+            hand_size: config.hand_size,
+            max_hints: config.clue_economy.max_hints.max(config.num_hints) as usize,
+            fuses: config.num_fuses as usize,
+            deck_size: config.variant.deck().len(),
+        }
+    }
+
+    pub fn observation_len(&self) -> usize {
+        let (players, hand) = (self.players, self.hand_size);
+        (players - 1) * hand * CARD_COUNT
+            + players * hand * (CARD_COUNT + 1)
+            + CARD_COUNT
+            + CARD_COUNT * MAX_COPIES
+            + self.max_hints
+            + 1
+            + self.fuses
+            + self.deck_size
+            + self.last_action_len()
+    }
+
+    fn last_action_len(&self) -> usize {
+        self.players + 4 + self.hand_size + self.players + HINT_COUNT + CARD_COUNT + 1
+    }
+
+    pub fn action_count(&self) -> usize {
+        2 * self.hand_size + (self.players - 1) * HINT_COUNT
+    }
+
+    /// The action of an index, for the player whose turn it is
+    pub fn action(&self, index: usize, player: PlayerIndex) -> Option<PlayerAction> {
+        let hand = self.hand_size;
+        if index < hand {
+            return Some(PlayerAction::PlayCard(SlotIndex(index)));
+        }
+        if index < 2 * hand {
+            return Some(PlayerAction::DiscardCard(SlotIndex(index - hand)));
+        }
+
+        let (seat, hint) = (
+            (index - 2 * hand) / HINT_COUNT + 1,
+            (index - 2 * hand) % HINT_COUNT,
+        );
+        if seat >= self.players {
+            return None;
+        }
+        let teammate = PlayerIndex((player.0 + seat) % self.players);
+        let hint = if hint < SUIT_COUNT {
+            HintAction::SameSuit(CardSuit::from_usize(hint))
+        } else {
+            HintAction::SameFace(CardFace::from_usize(hint - SUIT_COUNT))
+        };
+        Some(PlayerAction::GiveHint(teammate, hint))
+    }
+
+    /// The index of an action of the player whose turn it is, slot moves have none
+    pub fn action_index(&self, action: &PlayerAction, player: PlayerIndex) -> Option<usize> {
+        let hand = self.hand_size;
+        match *action {
+            PlayerAction::PlayCard(SlotIndex(slot)) if slot < hand => Some(slot),
+            PlayerAction::DiscardCard(SlotIndex(slot)) if slot < hand => Some(hand + slot),
+            PlayerAction::GiveHint(teammate, hint) => {
+                let seat = self.seat(player, teammate).filter(|&seat| seat > 0)?;
+                let hint = match hint {
+                    HintAction::SameSuit(suit) => suit.into_usize(),
+                    HintAction::SameFace(CardFace::Start) => return None,
+                    HintAction::SameFace(face) => SUIT_COUNT + face.into_usize(),
+                };
+                Some(2 * hand + (seat - 1) * HINT_COUNT + hint)
+            }
+            _ => None,
+        }
+    }
+
+    /// How many turns after `observer` a player acts
+    fn seat(&self, observer: PlayerIndex, player: PlayerIndex) -> Option<usize> {
+        (player.0 < self.players).then_some((player.0 + self.players - observer.0) % self.players)
+    }
+
+    /// The observation of the snapshot's player, `last_event` being the last event
+    /// of their log
+    pub fn encode(
+        &self,
+        snapshot: &GameStateSnapshot,
+        last_event: Option<&GameSnapshotEvent>,
+    ) -> Vec<f32> {
+        let mut vector = Vec::with_capacity(self.observation_len());
+        let observer = snapshot.this_client_player_index;
+        let seats = (0..self.players)
+            .map(|seat| &snapshot.players[(observer.0 + seat) % self.players])
+            .collect_vec();
+
+        for player in &seats[1..] {
+            let ClientPlayerView::Teammate { hand, .. } = player else {
+                unreachable!("only the observer's hand is hidden");
+            };
+            for slot in pad(hand, self.hand_size) {
+                push_one_hot(
+                    &mut vector,
+                    CARD_COUNT,
+                    slot.map(|slot| card_index(&slot.card)),
+                );
+            }
+        }
+
+        for player in &seats {
+            let hints = match player {
+                ClientPlayerView::Me { hand, .. } => pad(hand, self.hand_size)
+                    .map(|slot| slot.map(|slot| slot.hints))
+                    .collect_vec(),
+                ClientPlayerView::Teammate { hand, .. } => pad(hand, self.hand_size)
+                    .map(|slot| slot.map(|slot| slot.hints))
+                    .collect(),
+            };
+            for hints in hints {
+                match hints {
+                    Some(hints) => {
+                        vector.extend(all_cards().map(|card| bit(hints.is_possible(&card))));
+                        vector.push(bit(hints.is_touched()));
+                    }
+                    None => push_zeros(&mut vector, CARD_COUNT + 1),
+                }
+            }
+        }
+
+        vector.extend(all_cards().map(|card| bit(snapshot.played_cards.contains(&card))));
+
+        for card in all_cards() {
+            let discarded = snapshot.discard_pile.iter().filter(|c| **c == card).count();
+            push_count(&mut vector, MAX_COPIES, discarded);
+        }
+
+        push_count(
+            &mut vector,
+            self.max_hints,
+            snapshot.remaining_hint_count as usize,
+        );
+        vector.push(bit(snapshot.has_half_hint));
+        push_count(
+            &mut vector,
+            self.fuses,
+            snapshot.remaining_bomb_count as usize,
+        );
+        push_count(
+            &mut vector,
+            self.deck_size,
+            snapshot.draw_pile_count as usize,
+        );
+
+        match last_event {
+            Some(event) => self.encode_event(&mut vector, observer, event),
+            None => push_zeros(&mut vector, self.last_action_len()),
+        }
+
+        debug_assert_eq!(vector.len(), self.observation_len());
+        vector
+    }
+
+    fn encode_event(
+        &self,
+        vector: &mut Vec<f32>,
+        observer: PlayerIndex,
+        event: &GameSnapshotEvent,
+    ) {
+        let seat = |player| self.seat(observer, player);
+        push_one_hot(vector, self.players, seat(event.event_player_index));
+
+        let (kind, slot, target, suit, face) = match event.event_action {
+            PlayerAction::PlayCard(slot) => (Some(0), Some(slot.0), None, None, None),
+            PlayerAction::DiscardCard(slot) => (Some(1), Some(slot.0), None, None, None),
+            PlayerAction::GiveHint(teammate, HintAction::SameSuit(suit)) => {
+                (Some(2), None, seat(teammate), Some(suit.into_usize()), None)
+            }
+            PlayerAction::GiveHint(teammate, HintAction::SameFace(face)) => {
+                (Some(3), None, seat(teammate), None, Some(face.into_usize()))
+            }
+            PlayerAction::MoveSlot(_, _, _) => (None, None, None, None, None),
+        };
+        push_one_hot(vector, 4, kind);
+        push_one_hot(
+            vector,
+            self.hand_size,
+            slot.filter(|&slot| slot < self.hand_size),
+        );
+        push_one_hot(vector, self.players, target);
+        push_one_hot(vector, SUIT_COUNT, suit);
+        push_one_hot(vector, HINT_FACE_COUNT, face);
+
+        let card = event.effects.iter().find_map(|effect| match effect {
+            GameEffect::PlaceOnBoard(card) | GameEffect::AddToDiscard(card) => Some(card),
+            _ => None,
+        });
+        push_one_hot(vector, CARD_COUNT, card.map(card_index));
+        vector.push(bit(event.effects.contains(&GameEffect::BurnFuse)));
+    }
+}
+
+/// The slots of a hand, as many as a full hand holds
+fn pad<T>(hand: &[Option<T>], hand_size: usize) -> impl Iterator<Item = Option<&T>> {
+    (0..hand_size).map(|slot| hand.get(slot).and_then(Option::as_ref))
+}
+
+fn bit(value: bool) -> f32 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn push_zeros(vector: &mut Vec<f32>, len: usize) {
+    vector.extend((0..len).map(|_| 0.0));
+}
+
+fn push_one_hot(vector: &mut Vec<f32>, len: usize, index: Option<usize>) {
+    vector.extend((0..len).map(|i| bit(Some(i) == index)));
+}
+
+/// One value per unit of the count, up to `len`
+fn push_count(vector: &mut Vec<f32>, len: usize, count: usize) {
+    vector.extend((0..len).map(|i| bit(i < count)));
+}
+
+/// Why the environment couldn't deal or play a step
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EnvError {
+    /// Out of the action space, see `Encoding::action_count`
+    InvalidActionIndex(usize),
+    /// Refused by the game engine, e.g. an illegal action
+    GameError(GameError),
+}
+
+impl From<GameError> for EnvError {
+    fn from(error: GameError) -> Self {
+        EnvError::GameError(error)
+    }
+}
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvError::InvalidActionIndex(index) => write!(f, "Invalid action index {}", index),
+            EnvError::GameError(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// What the player to act knows, and the actions they're allowed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    pub player: PlayerIndex,
+    /// See the module documentation for the layout
+    pub vector: Vec<f32>,
+    /// Whether each action index is legal
+    pub legal_actions: Vec<bool>,
+}
+
+/// A game played one action index at a time, see the module documentation
+pub struct HanabiEnv {
+    config: GameConfig,
+    encoding: Encoding,
+    game_log: GameLog,
+    names: Vec<String>,
+}
+
+impl HanabiEnv {
+    /// Deals the config's seed, decks are shuffled like the server does
+    pub fn new(config: GameConfig) -> Result<Self, EnvError> {
+        Ok(HanabiEnv {
+            encoding: Encoding::new(&config),
+            game_log: GameLog::new::<StdRng>(config.clone())?,
+            names: (0..config.num_players)
+                .map(|index| format!("player {}", index + 1))
+                .collect(),
+            config,
+        })
+    }
+
+    pub fn encoding(&self) -> &Encoding {
+        &self.encoding
+    }
+
+    /// Every action played so far, e.g. to replay the game
+    pub fn game_log(&self) -> &GameLog {
+        &self.game_log
+    }
+
+    /// Starts over with a new deal
+    pub fn reset(&mut self, seed: u64) -> Result<Observation, EnvError> {
+        self.config.seed = seed;
+        self.game_log = GameLog::new::<StdRng>(self.config.clone())?;
+        Ok(self.observation())
+    }
+
+    /// Plays an action of the player whose turn it is. The reward is the points it
+    /// scored, all the points of the game taken back when it burned the last fuse,
+    /// and it's done once the game is over.
+    pub fn step(&mut self, action: usize) -> Result<(Observation, f32, bool), EnvError> {
+        let game_state = self.game_log.current_game_state();
+        let player = game_state.current_player_index();
+        let action = self
+            .encoding
+            .action(action, player)
+            .ok_or(EnvError::InvalidActionIndex(action))?;
+
+        let score = game_state.played_cards.score();
+        self.game_log.log(player, action)?;
+        let game_state = self.game_log.current_game_state();
+
+        let reward = if game_state.remaining_bomb_count == 0 {
+            -(score as f32)
+        } else {
+            game_state.played_cards.score() as f32 - score as f32
+        };
+        Ok((self.observation(), reward, game_state.status.is_finished()))
+    }
+
+    pub fn observation(&self) -> Observation {
+        let game_state = self.game_log.current_game_state();
+        let player = game_state.current_player_index();
+        let snapshot = self
+            .game_log
+            .into_client_game_state(game_state, player, self.names.clone());
+        let last_event = self.game_log.last_client_event(player, self.names.clone());
+
+        Observation {
+            player,
+            vector: self.encoding.encode(&snapshot, last_event.as_ref()),
+            legal_actions: self.legal_action_mask(),
+        }
+    }
+
+    /// Whether each action index is legal for the player whose turn it is, none
+    /// once the game is over
+    pub fn legal_action_mask(&self) -> Vec<bool> {
+        let game_state = self.game_log.current_game_state();
+        let player = game_state.current_player_index();
+        let mut mask = vec![false; self.encoding.action_count()];
+        if game_state.status.is_finished() {
+            return mask;
+        }
+        for action in game_state.legal_actions(player) {
+            if let Some(index) = self.encoding.action_index(&action, player) {
+                mask[index] = true;
+            }
+        }
+        mask
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn test_action_indices_round_trip() {
+        let encoding = Encoding::new(&GameConfig::new(4, 0));
+        assert_eq!(encoding.action_count(), 2 * 4 + 3 * 12);
+
+        for player in 0..4 {
+            let player = PlayerIndex(player);
+            for index in 0..encoding.action_count() {
+                let action = encoding.action(index, player).unwrap();
+                assert_eq!(encoding.action_index(&action, player), Some(index));
+            }
+            assert_eq!(encoding.action(encoding.action_count(), player), None);
+            let start_hint = HintAction::SameFace(CardFace::Start);
+            let teammate = PlayerIndex((player.0 + 1) % 4);
+            assert_eq!(
+                encoding.action_index(&PlayerAction::GiveHint(teammate, start_hint), player),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_random_games_follow_the_masks() {
+        let mut env = HanabiEnv::new(GameConfig::new(3, 0)).unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for seed in 0..5 {
+            let mut observation = env.reset(seed).unwrap();
+            let mut total = 0.0;
+            loop {
+                assert_eq!(observation.vector.len(), env.encoding().observation_len());
+                let legal = observation
+                    .legal_actions
+                    .iter()
+                    .positions(|legal| *legal)
+                    .collect_vec();
+                let action = *legal.choose(&mut rng).unwrap();
+
+                let (next, reward, done) = env.step(action).unwrap();
+                total += reward;
+                observation = next;
+                if done {
+                    break;
+                }
+            }
+
+            // random players often bomb out, losing their points
+            let game_state = env.game_log().current_game_state();
+            let score = match game_state.remaining_bomb_count {
+                0 => 0,
+                _ => game_state.played_cards.score(),
+            };
+            assert_eq!(total, score as f32);
+            assert!(observation.legal_actions.iter().all(|legal| !legal));
+            assert!(matches!(
+                env.step(0),
+                Err(EnvError::GameError(GameError::GameOver(_)))
+            ));
+            let action_count = env.encoding().action_count();
+            assert_eq!(
+                env.step(action_count),
+                Err(EnvError::InvalidActionIndex(action_count))
+            );
+        }
+    }
+
+    #[test]
+    fn test_observation_shows_the_teammates_cards() {
+        let mut env = HanabiEnv::new(GameConfig::new(2, 0)).unwrap();
+        let observation = env.reset(3).unwrap();
+        let game_state = env.game_log().current_game_state();
+        let teammate = &game_state.players[1].hand;
+
+        for (slot, card) in teammate.iter().enumerate() {
+            let card = card.as_ref().unwrap().card;
+            let start = slot * CARD_COUNT;
+            let one_hot = &observation.vector[start..start + CARD_COUNT];
+            assert_eq!(one_hot.iter().sum::<f32>(), 1.0);
+            assert_eq!(one_hot[card_index(&card)], 1.0);
+        }
+
+        // a hint to the teammate shows up as the last action of their observation
+        let hint = PlayerAction::GiveHint(
+            PlayerIndex(1),
+            HintAction::SameFace(teammate[0].as_ref().unwrap().card.face),
+        );
+        let index = env.encoding().action_index(&hint, PlayerIndex(0)).unwrap();
+        let (observation, reward, done) = env.step(index).unwrap();
+        assert_eq!((reward, done), (0.0, false));
+        assert_eq!(observation.player, PlayerIndex(1));
+
+        let encoding = env.encoding();
+        let last_action =
+            &observation.vector[encoding.observation_len() - encoding.last_action_len()..];
+        // the hinter acted one seat before, the hinted player is the observer
+        assert_eq!(&last_action[..2], &[0.0, 1.0]);
+        assert_eq!(&last_action[2..6], &[0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(&last_action[11..13], &[1.0, 0.0]);
+    }
+}
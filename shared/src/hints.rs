@@ -0,0 +1,207 @@
+use enum_map::Enum;
+use serde::Deserialize;
+use strum::IntoEnumIterator;
+
+use crate::model::{Card, CardFace, CardSuit, Hint, HintAction, HintKnowledge};
+
+const FACE_COUNT: usize = <CardFace as Enum>::LENGTH;
+
+/// Every suit and face combination, whether or not the variant deals it
+const ALL_CARDS: u64 = (1 << (<CardSuit as Enum>::LENGTH * FACE_COUNT)) - 1;
+
+fn card_bit(card: &Card) -> u64 {
+    1 << (card.suit.into_usize() * FACE_COUNT + card.face.into_usize())
+}
+
+fn all_cards() -> impl Iterator<Item = Card> {
+    CardSuit::iter().flat_map(|suit| CardFace::iter().map(move |face| Card { face, suit }))
+}
+
+impl Default for HintKnowledge {
+    fn default() -> Self {
+        HintKnowledge {
+            possible: ALL_CARDS,
+            suits: 0,
+            faces: 0,
+            not_suits: 0,
+            not_faces: 0,
+        }
+    }
+}
+
+impl HintKnowledge {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Narrows down the possible cards, receiving the same hint twice changes nothing
+    pub fn add(&mut self, hint: Hint) {
+        let (action, touched) = match hint {
+            Hint::IsSuit(suit) => {
+                self.suits |= 1 << suit.into_usize();
+                (HintAction::SameSuit(suit), true)
+            }
+            Hint::IsNotSuit(suit) => {
+                self.not_suits |= 1 << suit.into_usize();
+                (HintAction::SameSuit(suit), false)
+            }
+            Hint::IsFace(face) => {
+                self.faces |= 1 << face.into_usize();
+                (HintAction::SameFace(face), true)
+            }
+            Hint::IsNotFace(face) => {
+                self.not_faces |= 1 << face.into_usize();
+                (HintAction::SameFace(face), false)
+            }
+        };
+
+        for card in all_cards() {
+            if action.touches(&card) != touched {
+                self.possible &= !card_bit(&card);
+            }
+        }
+    }
+
+    pub fn is_possible(&self, card: &Card) -> bool {
+        self.possible & card_bit(card) != 0
+    }
+
+    /// Has the card been named by a hint, as opposed to only being left out of some
+    pub fn is_touched(&self) -> bool {
+        self.suits != 0 || self.faces != 0
+    }
+
+    /// Suits the card was hinted as
+    pub fn suit_hints(&self) -> impl Iterator<Item = CardSuit> + '_ {
+        CardSuit::iter().filter(|suit| self.suits & (1 << suit.into_usize()) != 0)
+    }
+
+    /// Faces the card was hinted as
+    pub fn face_hints(&self) -> impl Iterator<Item = CardFace> + '_ {
+        CardFace::iter().filter(|face| self.faces & (1 << face.into_usize()) != 0)
+    }
+
+    /// Hints naming the card, each suit then each face once
+    pub fn positive_hints(&self) -> impl Iterator<Item = Hint> + '_ {
+        self.suit_hints()
+            .map(Hint::IsSuit)
+            .chain(self.face_hints().map(Hint::IsFace))
+    }
+
+    /// Hints leaving the card out, each suit then each face once
+    pub fn negative_hints(&self) -> impl Iterator<Item = Hint> + '_ {
+        CardSuit::iter()
+            .filter(|suit| self.not_suits & (1 << suit.into_usize()) != 0)
+            .map(Hint::IsNotSuit)
+            .chain(
+                CardFace::iter()
+                    .filter(|face| self.not_faces & (1 << face.into_usize()) != 0)
+                    .map(Hint::IsNotFace),
+            )
+    }
+
+    /// Every distinct hint the card received, the order they were given in isn't kept
+    pub fn hints(&self) -> impl Iterator<Item = Hint> + '_ {
+        self.positive_hints().chain(self.negative_hints())
+    }
+}
+
+impl FromIterator<Hint> for HintKnowledge {
+    fn from_iter<T: IntoIterator<Item = Hint>>(iter: T) -> Self {
+        let mut knowledge = HintKnowledge::new();
+        for hint in iter {
+            knowledge.add(hint);
+        }
+        knowledge
+    }
+}
+
+impl From<Vec<Hint>> for HintKnowledge {
+    fn from(hints: Vec<Hint>) -> Self {
+        hints.into_iter().collect()
+    }
+}
+
+/// Slots used to keep every hint they received in a list, games stored back then
+/// still deserialize
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum StoredHints {
+    Hints(Vec<Hint>),
+    Knowledge {
+        possible: u64,
+        suits: u8,
+        faces: u8,
+        not_suits: u8,
+        not_faces: u8,
+    },
+}
+
+impl From<StoredHints> for HintKnowledge {
+    fn from(stored: StoredHints) -> Self {
+        match stored {
+            StoredHints::Hints(hints) => hints.into(),
+            StoredHints::Knowledge {
+                possible,
+                suits,
+                faces,
+                not_suits,
+                not_faces,
+            } => HintKnowledge {
+                possible,
+                suits,
+                faces,
+                not_suits,
+                not_faces,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CardFace::*;
+    use CardSuit::*;
+
+    #[test]
+    fn test_repeated_hints_are_kept_once() {
+        let mut knowledge = HintKnowledge::new();
+        knowledge.add(Hint::IsSuit(Red));
+        knowledge.add(Hint::IsNotFace(One));
+        knowledge.add(Hint::IsSuit(Red));
+        knowledge.add(Hint::IsNotFace(One));
+
+        assert!(knowledge.is_touched());
+        assert_eq!(
+            knowledge.hints().collect::<Vec<_>>(),
+            vec![Hint::IsSuit(Red), Hint::IsNotFace(One)]
+        );
+        assert!(knowledge.is_possible(&Card {
+            face: Two,
+            suit: Rainbow
+        }));
+        assert!(!knowledge.is_possible(&Card {
+            face: One,
+            suit: Red
+        }));
+        assert!(!knowledge.is_possible(&Card {
+            face: Two,
+            suit: Blue
+        }));
+    }
+
+    #[test]
+    fn test_deserializes_stored_hint_lists() {
+        let hints = vec![Hint::IsFace(Five), Hint::IsNotSuit(Blue)];
+        let stored = serde_json::to_string(&hints).unwrap();
+        let knowledge: HintKnowledge = serde_json::from_str(&stored).unwrap();
+        assert_eq!(knowledge, HintKnowledge::from(hints));
+
+        let serialized = serde_json::to_string(&knowledge).unwrap();
+        assert_eq!(
+            serde_json::from_str::<HintKnowledge>(&serialized).unwrap(),
+            knowledge
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use itertools::Itertools;
+
+use crate::model::{
+    Card, CardFace, CardKnowledge, CardSuit, ClientPlayerView, GameState, GameStateSnapshot,
+    HintKnowledge, PlayerIndex, Variant,
+};
+
+impl CardKnowledge {
+    /// Every card of the variant matching the hints, less the copies the viewer can see
+    pub fn new(variant: Variant, hints: &HintKnowledge, visible_cards: &[Card]) -> Self {
+        let suits = variant.suits();
+        let possible_cards = variant
+            .faces()
+            .into_iter()
+            .flat_map(|face| suits.iter().map(move |&suit| Card { face, suit }))
+            .filter(|card| hints.is_possible(card))
+            .map(|card| {
+                let seen = visible_cards.iter().filter(|c| **c == card).count();
+                (card, variant.copies(&card).saturating_sub(seen))
+            })
+            .filter(|(_, unseen)| *unseen > 0)
+            .collect();
+
+        CardKnowledge { possible_cards }
+    }
+
+    pub fn possible_cards(&self) -> impl Iterator<Item = Card> + '_ {
+        self.possible_cards.iter().map(|(card, _)| *card)
+    }
+
+    pub fn is_possible(&self, card: &Card) -> bool {
+        self.possible_cards().contains(card)
+    }
+
+    /// Copies the slot could be, summed over every possible card
+    pub fn unseen_copies(&self) -> usize {
+        self.possible_cards.iter().map(|(_, unseen)| unseen).sum()
+    }
+
+    pub fn known_card(&self) -> Option<Card> {
+        self.possible_cards().exactly_one().ok()
+    }
+
+    pub fn known_face(&self) -> Option<CardFace> {
+        self.possible_cards()
+            .map(|c| c.face)
+            .unique()
+            .exactly_one()
+            .ok()
+    }
+
+    pub fn known_suit(&self) -> Option<CardSuit> {
+        self.possible_cards()
+            .map(|c| c.suit)
+            .unique()
+            .exactly_one()
+            .ok()
+    }
+}
+
+impl GameState {
+    /// Cards a player can see: the board, the discard pile and every other hand
+    pub fn visible_cards(&self, viewer: PlayerIndex) -> Vec<Card> {
+        let teammates_cards = self
+            .players
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != viewer.0)
+            .flat_map(|(_, player)| player.hand.iter().flatten().map(|slot| slot.card));
+
+        self.played_cards
+            .cards()
+            .chain(self.discard_pile.iter().copied())
+            .chain(teammates_cards)
+            .collect()
+    }
+
+    /// What a player can tell about each card in their own hand
+    pub fn hand_knowledge(&self, player: PlayerIndex) -> Vec<Option<CardKnowledge>> {
+        let visible_cards = self.visible_cards(player);
+        let variant = self.config.variant;
+
+        self.players
+            .get(player.0)
+            .map(|p| {
+                p.hand
+                    .iter()
+                    .map(|slot| {
+                        let slot = slot.as_ref()?;
+                        Some(CardKnowledge::new(variant, &slot.hints, &visible_cards))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl GameStateSnapshot {
+    /// Cards this client can see: the board, the discard pile and the teammates' hands
+    pub fn visible_cards(&self) -> Vec<Card> {
+        let teammates_cards = self.players.iter().flat_map(|player| match player {
+            ClientPlayerView::Teammate { hand, .. } => {
+                hand.iter().flatten().map(|slot| slot.card).collect_vec()
+            }
+            ClientPlayerView::Me { .. } => vec![],
+        });
+
+        self.played_cards
+            .cards()
+            .chain(self.discard_pile.iter().copied())
+            .chain(teammates_cards)
+            .collect()
+    }
+
+    /// What this client can tell about each card in their own hand
+    pub fn hand_knowledge(&self) -> Vec<Option<CardKnowledge>> {
+        let visible_cards = self.visible_cards();
+        let variant = self.game_config.variant;
+
+        match self.players.get(self.this_client_player_index.0) {
+            Some(ClientPlayerView::Me { hand, .. }) => hand
+                .iter()
+                .map(|slot| {
+                    let slot = slot.as_ref()?;
+                    Some(CardKnowledge::new(variant, &slot.hints, &visible_cards))
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{
+        GameConfig, GameStatus, Hint, HintAction, PlayedStacks, Player, PlayerAction, Slot,
+    };
+    use CardFace::*;
+    use CardSuit::*;
+
+    fn card(face: CardFace, suit: CardSuit) -> Card {
+        Card { face, suit }
+    }
+
+    fn card_slot(face: CardFace, suit: CardSuit) -> Option<Slot> {
+        Some(Slot {
+            card: card(face, suit),
+            hints: HintKnowledge::new(),
+            hint_history: vec![],
+            draw_number: 0,
+        })
+    }
+
+    #[test]
+    fn test_hints_narrow_down_possible_cards() {
+        let variant = Variant::Standard;
+        let hints = HintKnowledge::from(vec![Hint::IsFace(Five), Hint::IsNotSuit(Red)]);
+        let knowledge = CardKnowledge::new(variant, &hints, &[]);
+
+        assert_eq!(knowledge.known_face(), Some(Five));
+        assert_eq!(knowledge.known_suit(), None);
+        assert_eq!(knowledge.unseen_copies(), 4);
+        assert!(!knowledge.is_possible(&card(Five, Red)));
+
+        // every other five is in sight, so this one has to be the white five
+        let visible_cards = [card(Five, Green), card(Five, Yellow), card(Five, Blue)];
+        let knowledge = CardKnowledge::new(variant, &hints, &visible_cards);
+        assert_eq!(knowledge.known_card(), Some(card(Five, White)));
+    }
+
+    #[test]
+    fn test_hand_knowledge_from_each_player() {
+        let mut game_state = GameState {
+            draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
+            played_cards: PlayedStacks::from(vec![card(One, Blue)]),
+            discard_pile: vec![card(One, Green)],
+            players: vec![
+                Player {
+                    hand: vec![card_slot(One, Green), card_slot(Five, Red)],
+                },
+                Player {
+                    hand: vec![card_slot(One, Green), card_slot(One, Blue)],
+                },
+            ],
+            remaining_bomb_count: 3,
+            remaining_hint_count: 8,
+            has_half_hint: false,
+            turn: 0,
+            last_turn: None,
+            outcome: None,
+            config: GameConfig::new(2, 0),
+            status: GameStatus::Playing,
+        };
+
+        for player in [PlayerIndex(0), PlayerIndex(1)] {
+            let teammate = PlayerIndex(1 - player.0);
+            let action = PlayerAction::GiveHint(player, HintAction::SameFace(One));
+            game_state.turn = teammate.0 as u8;
+            let effects = game_state.play(action).unwrap();
+            game_state.run_effects(effects).unwrap();
+        }
+
+        // player 0 sees the green 1s on the board, the discards and the teammate's hand
+        let knowledge = game_state.hand_knowledge(PlayerIndex(0));
+        assert_eq!(knowledge.len(), 2);
+        let green_one = knowledge[0].as_ref().unwrap();
+        assert!(green_one.is_possible(&card(One, Green)));
+        assert_eq!(
+            green_one
+                .possible_cards
+                .iter()
+                .find(|(c, _)| *c == card(One, Green)),
+            Some(&(card(One, Green), 1))
+        );
+        assert!(!knowledge[1].as_ref().unwrap().is_possible(&card(One, Red)));
+
+        // player 1 can't see their own blue 1, only the one on the board
+        let knowledge = game_state.hand_knowledge(PlayerIndex(1));
+        assert_eq!(
+            knowledge[1]
+                .as_ref()
+                .unwrap()
+                .possible_cards
+                .iter()
+                .find(|(c, _)| *c == card(One, Blue)),
+            Some(&(card(One, Blue), 2))
+        );
+    }
+}
@@ -1,6 +1,22 @@
+pub mod actions;
+pub mod bot;
+pub mod client_log;
 pub mod client_logic;
+pub mod conventions;
+pub mod env;
+pub mod hints;
+pub mod knowledge;
 pub mod logic;
 pub mod model;
+pub mod rules;
+pub mod sampling;
+pub mod search;
+pub mod solver;
+pub mod stacks;
+pub mod variant;
+
+#[cfg(test)]
+mod test_data;
 
 #[cfg(test)]
 mod tests {
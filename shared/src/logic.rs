@@ -1,55 +1,101 @@
-use std::iter;
+use std::{fmt, iter};
 
 use itertools::Itertools;
 use rand::{Rng, SeedableRng};
-use strum::IntoEnumIterator;
 
 use crate::model::{
-    Card, CardFace, CardSuit, GameConfig, GameEffect, GameOutcome, GameState, Hint, HintAction,
-    PlayedCardResult, Player, PlayerAction, PlayerIndex, Slot, SlotIndex,
+    Card, CardClass, CardFace, CardSuit, GameConfig, GameEffect, GameError, GameOutcome, GameState,
+    GameStatus, Hint, HintAction, HintKnowledge, HintOrigin, PlayedCardResult, PlayedStacks,
+    Player, PlayerAction, PlayerIndex, RuleViolation, Slot, SlotIndex, Variant,
 };
+use crate::rules;
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::GameOver(outcome) => write!(f, "Game is already over: {:?}", outcome),
+            GameError::NotYourTurn {
+                current_player: PlayerIndex(index),
+            } => write!(f, "It's not your turn, waiting for player {}", index),
+            GameError::NotYourHand => write!(f, "You can only move cards in your own hand"),
+            GameError::InvalidConfig => write!(f, "The game can't be dealt with this config"),
+            GameError::InvalidPlayer(PlayerIndex(index)) => {
+                write!(f, "Invalid player index {}", index)
+            }
+            GameError::InvalidSlot(SlotIndex(index)) => write!(f, "Invalid slot index {}", index),
+            GameError::CannotMoveToSameSlot => write!(f, "Cannot move slot to itself"),
+            GameError::NoHintsLeft => write!(f, "Not enough hints"),
+            GameError::HintNotAllowed(hint) => {
+                write!(f, "{:?} can't be given in this variant", hint)
+            }
+            GameError::RuleViolation(violation) => write!(f, "{}", violation),
+            GameError::InconsistentEffect(effect) => {
+                write!(f, "Logic error: {:?} doesn't apply to the game", effect)
+            }
+        }
+    }
+}
 
-impl GameState {
-    pub fn start_with_seed<R: SeedableRng + Rng>(config: &GameConfig) -> Result<GameState, String> {
-        let mut game = GameState {
-            draw_pile: new_seeded_deck::<R>(config.seed),
-            discard_pile: Vec::new(),
-            last_turn: None,
-            played_cards: Vec::new(),
-            players: (0..config.num_players)
-                .into_iter()
-                .map(|_index| Player {
-                    hand: (0..config.hand_size).map(|_slot_index| None).collect_vec(),
-                })
-                .collect(),
-            remaining_bomb_count: config.num_fuses,
-            remaining_hint_count: config.num_hints,
-            turn: config.starting_player.0 as u8,
-            outcome: None,
-        };
+impl From<RuleViolation> for GameError {
+    fn from(violation: RuleViolation) -> Self {
+        GameError::RuleViolation(violation)
+    }
+}
 
-        use GameEffect::*;
-        let init_effects = (0..config.hand_size)
-            .flat_map(move |slot_index| {
-                (0..config.num_players).map(move |player_index| {
-                    DrawCard(PlayerIndex(player_index), SlotIndex(slot_index))
-                })
+/// How a game with this table ends, if it does. Clients replaying a log work it
+/// out the same way from what they see.
+pub(crate) fn game_outcome(
+    config: &GameConfig,
+    status: GameStatus,
+    remaining_bomb_count: u8,
+    played_cards: &PlayedStacks,
+    discard_pile: &[Card],
+) -> Option<GameOutcome> {
+    let variant = config.variant;
+    match (
+        status,
+        remaining_bomb_count,
+        variant.is_all_sets_complete(played_cards),
+    ) {
+        (GameStatus::Finished(outcome), _, _) => Some(outcome),
+        (_, _, true) => Some(GameOutcome::Win),
+        (_, 0, false) => Some(GameOutcome::Fail {
+            score: played_cards.score(),
+        }),
+        _ if config.end_when_perfect_lost
+            && variant.max_achievable_score(played_cards, discard_pile) < variant.max_score() =>
+        {
+            Some(GameOutcome::Fail {
+                score: played_cards.score(),
             })
-            .collect();
+        }
+        (GameStatus::LastRound { turns_remaining: 0 }, _, _) => Some(GameOutcome::Fail {
+            score: played_cards.score(),
+        }),
+        (_, _, _) => None,
+    }
+}
 
-        game.run_effects(init_effects)?;
-        return Ok(game);
+impl GameState {
+    pub fn start_with_seed<R: SeedableRng + Rng>(
+        config: &GameConfig,
+    ) -> Result<GameState, GameError> {
+        Self::start_with_deck::<R>(config, new_seeded_deck::<R>(config.variant, config.seed))
     }
 
     pub fn start_with_deck<R: SeedableRng + Rng>(
         config: &GameConfig,
         deck: Vec<Card>,
-    ) -> Result<GameState, String> {
+    ) -> Result<GameState, GameError> {
+        if config.num_players == 0 || config.num_players * config.hand_size > deck.len() {
+            return Err(GameError::InvalidConfig);
+        }
+
         let mut game = GameState {
             draw_pile: deck,
             discard_pile: Vec::new(),
             last_turn: None,
-            played_cards: Vec::new(),
+            played_cards: PlayedStacks::new(),
             players: (0..config.num_players)
                 .into_iter()
                 .map(|_index| Player {
@@ -58,8 +104,11 @@ impl GameState {
                 .collect(),
             remaining_bomb_count: config.num_fuses,
             remaining_hint_count: config.num_hints,
+            has_half_hint: false,
             turn: config.starting_player.0 as u8,
             outcome: None,
+            config: config.clone(),
+            status: GameStatus::WaitingToStart,
         };
 
         use GameEffect::*;
@@ -72,21 +121,50 @@ impl GameState {
             .collect();
 
         game.run_effects(init_effects)?;
+        // the outcome is checked after every card dealt, a config without fuses
+        // has lost before the first turn
+        if game.status != GameStatus::WaitingToStart {
+            return Ok(game);
+        }
+        game.status = GameStatus::Playing;
+
+        // everything was dealt, each player still gets a turn
+        if game.draw_pile.is_empty() {
+            let last_turn = game.turn + game.players.len() as u8 - 1;
+            game.run_effect(MarkLastTurn(last_turn))?;
+        }
         return Ok(game);
     }
 
-    // precondition: assumes the the action was taken by the current player
-    pub fn play(&self, action: PlayerAction) -> Result<Vec<GameEffect>, String> {
-        if let Some(outcome) = &self.outcome {
-            return Err(format!("Game is already over: {:?}", outcome));
+    /// Only the current player can act, except for moving cards around in one's own hand
+    /// which is allowed at any time
+    pub fn check_actor(&self, actor: PlayerIndex, action: &PlayerAction) -> Result<(), GameError> {
+        match action {
+            PlayerAction::MoveSlot(hand_owner, _, _) if *hand_owner != actor => {
+                Err(GameError::NotYourHand)
+            }
+            PlayerAction::MoveSlot(_, _, _) => Ok(()),
+            _ if actor != self.current_player_index() => Err(GameError::NotYourTurn {
+                current_player: self.current_player_index(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    // precondition: assumes the the action was taken by the current player, see `check_actor`
+    pub fn play(&self, action: PlayerAction) -> Result<Vec<GameEffect>, GameError> {
+        if let GameStatus::Finished(outcome) = self.status {
+            return Err(GameError::GameOver(outcome));
         }
 
+        self.validate_action(&action)?;
+
         use GameEffect::*;
         let player_index = PlayerIndex(self.turn as usize % self.players.len());
         let current_player = self
             .players
-            .get(self.turn as usize % self.players.len())
-            .ok_or_else(|| "Invalid player index".to_string())?;
+            .get(player_index.0)
+            .ok_or(GameError::InvalidPlayer(player_index))?;
 
         fn draw_card_effect(
             game_state: &GameState,
@@ -111,8 +189,8 @@ impl GameState {
         }
 
         fn next_turn_effect(game_state: &GameState) -> GameEffect {
-            match (game_state.turn, game_state.last_turn) {
-                (current_turn, Some(last_turn)) if current_turn >= last_turn => {
+            match game_state.status {
+                GameStatus::LastRound { turns_remaining } if turns_remaining <= 1 => {
                     GameEffect::LastTurn
                 }
                 _ => GameEffect::NextTurn(game_state.turn + 1),
@@ -124,8 +202,8 @@ impl GameState {
                 let slot = current_player
                     .hand
                     .get(slot_index)
-                    .and_then(|s| s.as_ref().map(|s| s))
-                    .ok_or_else(|| "Invalid slot index".to_string())?;
+                    .and_then(|s| s.as_ref())
+                    .ok_or(GameError::InvalidSlot(SlotIndex(slot_index)))?;
                 let play_result = self.check_play(&slot.card);
 
                 return Ok(match play_result {
@@ -140,9 +218,16 @@ impl GameState {
                     PlayedCardResult::CompletedSet => [
                         RemoveCard(player_index, SlotIndex(slot_index)),
                         PlaceOnBoard(slot.card),
-                        IncHint,
                     ]
                     .into_iter()
+                    .chain(
+                        // with strict rules the clue is lost when all the hints are available,
+                        // and it would tell a hidden play went well
+                        (self.config.clue_economy.hint_on_completed_set
+                            && !self.config.variant.hides_play_results()
+                            && self.validate_effect(&IncHint).is_ok())
+                        .then_some(IncHint),
+                    )
                     .chain(draw_card_effect(self, player_index, SlotIndex(slot_index)))
                     .chain(iter::once(next_turn_effect(self)))
                     .collect_vec(),
@@ -161,13 +246,19 @@ impl GameState {
                 let slot = current_player
                     .hand
                     .get(slot_index)
-                    .and_then(|s| s.as_ref().map(|s| s))
-                    .ok_or_else(|| "Invalid slot index".to_string())?;
+                    .and_then(|s| s.as_ref())
+                    .ok_or(GameError::InvalidSlot(SlotIndex(slot_index)))?;
+
+                let hint_recovered = if self.config.clue_economy.half_hint_on_discard {
+                    IncHalfHint
+                } else {
+                    IncHint
+                };
 
                 return Ok([
                     RemoveCard(player_index, SlotIndex(slot_index)),
                     AddToDiscard(slot.card),
-                    IncHint,
+                    hint_recovered,
                 ]
                 .into_iter()
                 .chain(draw_card_effect(self, player_index, SlotIndex(slot_index)))
@@ -175,53 +266,36 @@ impl GameState {
                 .collect_vec());
             }
             PlayerAction::GiveHint(PlayerIndex(hinted_player_index), hint_type) => {
-                use HintAction::*;
+                if !rules::can_hint(self.remaining_hint_count) {
+                    return Err(GameError::NoHintsLeft);
+                }
 
-                if self.remaining_hint_count <= 0 {
-                    return Err("Not enough hints".to_string());
+                let variant = self.config.variant;
+                if !variant.is_hint_allowed(&hint_type) {
+                    return Err(GameError::HintNotAllowed(hint_type));
                 }
 
                 let hinted_player = self
                     .players
                     .get(hinted_player_index)
-                    .ok_or_else(|| "Invalid player index".to_string())?;
+                    .ok_or(GameError::InvalidPlayer(PlayerIndex(hinted_player_index)))?;
 
+                let origin = HintOrigin {
+                    giver: self.current_player_index(),
+                    turn: self.turn,
+                };
                 let hints: Vec<GameEffect> = hinted_player
                     .hand
                     .iter()
                     .enumerate()
-                    .filter_map(|value| {
-                        if let (index, Some(slot)) = value {
-                            let slot_index = SlotIndex(index);
-                            match (slot.card.face, slot.card.suit, hint_type) {
-                                (face, _, SameFace(face_hint)) if face == face_hint => {
-                                    Some(HintCard(
-                                        PlayerIndex(hinted_player_index),
-                                        slot_index,
-                                        Hint::IsFace(face_hint),
-                                    ))
-                                }
-                                (_, suit, SameSuit(suit_hint)) if suit == suit_hint => {
-                                    Some(HintCard(
-                                        PlayerIndex(hinted_player_index),
-                                        slot_index,
-                                        Hint::IsSuit(suit_hint),
-                                    ))
-                                }
-                                (_, _, SameFace(face_hint)) => Some(HintCard(
-                                    PlayerIndex(hinted_player_index),
-                                    slot_index,
-                                    Hint::IsNotFace(face_hint),
-                                )),
-                                (_, _, SameSuit(suit_hint)) => Some(HintCard(
-                                    PlayerIndex(hinted_player_index),
-                                    slot_index,
-                                    Hint::IsNotSuit(suit_hint),
-                                )),
-                            }
-                        } else {
-                            None
-                        }
+                    .filter_map(|(index, slot)| {
+                        let slot = slot.as_ref()?;
+                        Some(HintCard(
+                            PlayerIndex(hinted_player_index),
+                            SlotIndex(index),
+                            hint_type.hint(variant.hint_touches(&slot.card, &hint_type)),
+                            origin,
+                        ))
                     })
                     .collect();
                 let hinted_effects = vec![DecHint, next_turn_effect(self)];
@@ -239,22 +313,22 @@ impl GameState {
                 let player = self
                     .players
                     .get(player_index)
-                    .ok_or_else(|| "Invalid player index".to_string())?;
+                    .ok_or(GameError::InvalidPlayer(PlayerIndex(player_index)))?;
 
                 let from_slot = player
                     .hand
                     .get(from_slot_index)
-                    .and_then(|s| s.as_ref().map(|s| s))
-                    .ok_or_else(|| "Invalid from slot index".to_string())?;
+                    .and_then(|s| s.as_ref())
+                    .ok_or(GameError::InvalidSlot(SlotIndex(from_slot_index)))?;
 
                 let to_slot = player
                     .hand
                     .get(to_slot_index)
-                    .and_then(|s| s.as_ref().map(|s| s))
-                    .ok_or_else(|| "Invalid to slot index".to_string())?;
+                    .and_then(|s| s.as_ref())
+                    .ok_or(GameError::InvalidSlot(SlotIndex(to_slot_index)))?;
 
                 if from_slot_index == to_slot_index {
-                    return Err("Cannot move slot to itself".to_string());
+                    return Err(GameError::CannotMoveToSameSlot);
                 }
 
                 return Ok(vec![GameEffect::MoveSlot(
@@ -267,57 +341,66 @@ impl GameState {
     }
 
     fn check_play(&self, card_played: &Card) -> PlayedCardResult {
-        // Is the previous required card already played? Good!
-        if let Some(required_card) = card_played.prev_card() {
-            // Note: If prev_card is None, then we skip this check.
-            if !self.played_cards.contains(&required_card) {
-                return PlayedCardResult::Rejected;
-            }
-        }
+        self.config
+            .variant
+            .check_play(&self.played_cards, card_played)
+    }
 
-        // Has this card already been played? Bad!
-        let has_same_card = self.played_cards.contains(card_played);
-        if has_same_card {
-            return PlayedCardResult::Rejected;
-        }
+    /// Size of the deck the game was dealt from, whatever the variant or deck used
+    fn num_cards_in_game(&self) -> usize {
+        let cards_in_hands = self
+            .players
+            .iter()
+            .flat_map(|p| p.hand.iter().flatten())
+            .count();
 
-        if card_played.is_final_set_card() {
-            PlayedCardResult::CompletedSet
-        } else {
-            PlayedCardResult::Accepted
-        }
+        self.draw_pile.len() + self.discard_pile.len() + self.played_cards.score() + cards_in_hands
     }
 
     pub fn check_game_outcome(&self) -> Option<GameOutcome> {
-        match (
-            self.turn,
-            self.last_turn,
+        game_outcome(
+            &self.config,
+            self.status,
             self.remaining_bomb_count,
-            self.is_all_sets_complete(),
-        ) {
-            (_, _, _, true) => Some(GameOutcome::Win),
-            (_, _, 0, false) => Some(GameOutcome::Fail {
-                score: self.played_cards.len(),
-            }),
-            (current_turn, Some(last_turn), _, _) if current_turn > last_turn => {
-                Some(GameOutcome::Fail {
-                    score: self.played_cards.len(),
-                })
-            }
-            (_, _, _, _) => None,
-        }
+            &self.played_cards,
+            &self.discard_pile,
+        )
     }
 
-    fn is_all_sets_complete(&self) -> bool {
-        for face in CardFace::iter() {
-            for suit in CardSuit::iter() {
-                let card = Card { face, suit };
-                if !self.played_cards.contains(&card) {
-                    return false;
-                }
-            }
+    /// The best score still reachable with the cards left in the game
+    pub fn max_achievable_score(&self) -> usize {
+        self.config
+            .variant
+            .max_achievable_score(&self.played_cards, &self.discard_pile)
+    }
+
+    pub fn classify_card(&self, card: &Card) -> CardClass {
+        self.config
+            .variant
+            .classify_card(&self.played_cards, &self.discard_pile, card)
+    }
+
+    /// Cards that can still be discarded (or burned) before running out of turns
+    /// to reach the max achievable score, negative once it's too late
+    pub fn pace(&self) -> isize {
+        pace(
+            self.played_cards.score(),
+            self.draw_pile.len(),
+            self.players.len(),
+            self.max_achievable_score(),
+        )
+    }
+
+    /// Turns left once the draw pile is empty, including the current one
+    pub fn turns_remaining(&self) -> Option<u8> {
+        self.status.turns_remaining()
+    }
+
+    fn next_turn(&mut self) {
+        self.turn += 1;
+        if let GameStatus::LastRound { turns_remaining } = &mut self.status {
+            *turns_remaining = turns_remaining.saturating_sub(1);
         }
-        return true;
     }
 
     pub fn current_round(&self) -> u8 {
@@ -334,36 +417,58 @@ impl GameState {
         }
     }
 
-    pub fn run_effects(&mut self, effects: Vec<GameEffect>) -> Result<(), String> {
+    pub fn run_effects(&mut self, effects: Vec<GameEffect>) -> Result<(), GameError> {
         for effect in effects {
             self.run_effect(effect)?;
         }
         Ok(())
     }
 
-    pub fn run_effect(&mut self, effect: GameEffect) -> Result<(), String> {
+    /// The slot an effect is about, effects from a corrupt replay can point anywhere
+    fn effect_slot_mut(
+        &mut self,
+        effect: GameEffect,
+        PlayerIndex(player_index): PlayerIndex,
+        SlotIndex(slot_index): SlotIndex,
+    ) -> Result<&mut Option<Slot>, GameError> {
+        self.players
+            .get_mut(player_index)
+            .and_then(|player| player.hand.get_mut(slot_index))
+            .ok_or(GameError::InconsistentEffect(effect))
+    }
+
+    pub fn run_effect(&mut self, effect: GameEffect) -> Result<(), GameError> {
+        self.validate_effect(&effect)?;
+
         match effect {
-            GameEffect::DrawCard(PlayerIndex(player_index), SlotIndex(slot_index)) => {
-                assert!(
-                    self.players[player_index].hand[slot_index].is_none(),
-                    "Slot is not empty"
-                );
-                let draw_number = num_cards().saturating_sub(self.draw_pile.len());
-
-                self.players[player_index].hand[slot_index] = Some(Slot {
-                    card: self
-                        .draw_pile
-                        .pop()
-                        .ok_or_else(|| "Logic error: No more cards to draw")?,
-                    hints: vec![],
+            GameEffect::DrawCard(player_index, slot_index) => {
+                let draw_number = self.num_cards_in_game() - self.draw_pile.len();
+                if self
+                    .effect_slot_mut(effect, player_index, slot_index)?
+                    .is_some()
+                {
+                    return Err(GameError::InconsistentEffect(effect));
+                }
+
+                let card = self
+                    .draw_pile
+                    .pop()
+                    .ok_or(GameError::InconsistentEffect(effect))?;
+                *self.effect_slot_mut(effect, player_index, slot_index)? = Some(Slot {
+                    card,
+                    hints: HintKnowledge::new(),
+                    hint_history: vec![],
                     draw_number,
                 });
             }
             GameEffect::MarkLastTurn(turn_count) => {
                 self.last_turn = Some(turn_count);
+                self.status = GameStatus::LastRound {
+                    turns_remaining: (turn_count + 1).saturating_sub(self.turn),
+                };
             }
-            GameEffect::RemoveCard(PlayerIndex(player_index), SlotIndex(slot_index)) => {
-                self.players[player_index].hand[slot_index] = None;
+            GameEffect::RemoveCard(player_index, slot_index) => {
+                *self.effect_slot_mut(effect, player_index, slot_index)? = None;
             }
             GameEffect::AddToDiscard(card) => {
                 self.discard_pile.push(card);
@@ -371,56 +476,77 @@ impl GameState {
             GameEffect::PlaceOnBoard(card) => {
                 self.played_cards.push(card);
             }
-            GameEffect::HintCard(PlayerIndex(player_index), SlotIndex(slot_index), hint) => {
-                self.players[player_index].hand[slot_index]
+            GameEffect::HintCard(player_index, slot_index, hint, origin) => {
+                let slot = self
+                    .effect_slot_mut(effect, player_index, slot_index)?
                     .as_mut()
-                    .ok_or_else(|| "No card to hint in slot index")?
-                    .hints
-                    .push(hint);
+                    .ok_or(GameError::InconsistentEffect(effect))?;
+                slot.hints.add(hint);
+                slot.hint_history.push((hint, origin));
             }
             GameEffect::DecHint => {
-                self.remaining_hint_count = self.remaining_hint_count - 1;
+                self.remaining_hint_count = self
+                    .remaining_hint_count
+                    .checked_sub(1)
+                    .ok_or(GameError::InconsistentEffect(effect))?;
             }
             GameEffect::IncHint => {
-                self.remaining_hint_count = self.remaining_hint_count + 1;
+                self.remaining_hint_count = self
+                    .remaining_hint_count
+                    .saturating_add(1)
+                    .min(self.config.clue_economy.max_hints);
             }
-            GameEffect::BurnFuse => {
-                self.remaining_bomb_count = self.remaining_bomb_count - 1;
+            GameEffect::IncHalfHint => {
+                if self.has_half_hint {
+                    self.has_half_hint = false;
+                    self.run_effect(GameEffect::IncHint)?;
+                } else if self.remaining_hint_count < self.config.clue_economy.max_hints {
+                    self.has_half_hint = true;
+                }
             }
-            GameEffect::NextTurn(_) => {
-                self.turn = self.turn + 1;
+            GameEffect::BurnFuse => {
+                self.remaining_bomb_count = self
+                    .remaining_bomb_count
+                    .checked_sub(1)
+                    .ok_or(GameError::InconsistentEffect(effect))?;
             }
-            GameEffect::LastTurn => {
-                self.turn = self.turn + 1;
-                self.outcome = self.check_game_outcome();
-                // TODO implement (noop for now)
+            GameEffect::NextTurn(_) | GameEffect::LastTurn => {
+                self.next_turn();
             }
             GameEffect::MoveSlot(
                 PlayerIndex(player_index),
                 SlotIndex(from_slot_index),
                 SlotIndex(new_slot_index),
             ) => {
+                let hand = self
+                    .players
+                    .get_mut(player_index)
+                    .map(|player| &mut player.hand)
+                    .filter(|hand| from_slot_index.max(new_slot_index) < hand.len())
+                    .ok_or(GameError::InconsistentEffect(effect))?;
+
                 if from_slot_index < new_slot_index {
-                    self.players[player_index].hand[from_slot_index..=new_slot_index]
-                        .rotate_left(1);
+                    hand[from_slot_index..=new_slot_index].rotate_left(1);
                 } else {
-                    self.players[player_index].hand[new_slot_index..=from_slot_index]
-                        .rotate_right(1);
+                    hand[new_slot_index..=from_slot_index].rotate_right(1);
                 }
             }
         }
 
         self.outcome = self.check_game_outcome();
+        if let Some(outcome) = self.outcome {
+            self.status = GameStatus::Finished(outcome);
+        }
 
         return Ok(());
     }
 }
 
 impl Card {
-    fn prev_face(&self) -> Option<CardFace> {
+    pub(crate) fn prev_face(&self) -> Option<CardFace> {
         use CardFace::*;
         match self.face {
-            One => None,
+            One | Start => None,
             Two => Some(One),
             Three => Some(Two),
             Four => Some(Three),
@@ -428,7 +554,18 @@ impl Card {
         }
     }
 
-    fn prev_card(&self) -> Option<Card> {
+    pub(crate) fn next_face(&self) -> Option<CardFace> {
+        use CardFace::*;
+        match self.face {
+            One => Some(Two),
+            Two => Some(Three),
+            Three => Some(Four),
+            Four => Some(Five),
+            Five | Start => None,
+        }
+    }
+
+    pub(crate) fn prev_card(&self) -> Option<Card> {
         if let Some(face) = self.prev_face() {
             Some(Card {
                 face,
@@ -439,55 +576,60 @@ impl Card {
         }
     }
 
-    fn is_final_set_card(&self) -> bool {
+    pub(crate) fn is_final_set_card(&self) -> bool {
         self.face == CardFace::Five
     }
 }
 
+impl GameStatus {
+    pub fn turns_remaining(&self) -> Option<u8> {
+        match self {
+            GameStatus::LastRound { turns_remaining } => Some(*turns_remaining),
+            _ => None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, GameStatus::Finished(_))
+    }
+}
+
+impl HintAction {
+    /// Color hints also touch rainbow cards, no variant changes who a hint touches
+    pub fn touches(&self, card: &Card) -> bool {
+        match self {
+            HintAction::SameSuit(suit) => card.suit == *suit || card.suit == CardSuit::Rainbow,
+            HintAction::SameFace(face) => card.face == *face,
+        }
+    }
+
+    /// The hint a card receives from this hint action, depending on whether it was touched
+    pub fn hint(&self, touched: bool) -> Hint {
+        match (self, touched) {
+            (HintAction::SameSuit(suit), true) => Hint::IsSuit(*suit),
+            (HintAction::SameSuit(suit), false) => Hint::IsNotSuit(*suit),
+            (HintAction::SameFace(face), true) => Hint::IsFace(*face),
+            (HintAction::SameFace(face), false) => Hint::IsNotFace(*face),
+        }
+    }
+}
+
+pub fn pace(score: usize, draw_pile_count: usize, num_players: usize, max_score: usize) -> isize {
+    (score + draw_pile_count + num_players) as isize - max_score as isize
+}
+
 pub fn num_cards() -> usize {
-    CardFace::iter()
-        .flat_map(|face| {
-            CardSuit::iter().map(move |_suit| match face {
-                CardFace::One => 3,
-                CardFace::Two | CardFace::Three | CardFace::Four => 2,
-                CardFace::Five => 1,
-            })
-        })
-        .sum()
+    Variant::Standard.num_cards()
 }
 
 pub fn new_standard_deck() -> Vec<Card> {
-    let deck: Vec<Card> = CardFace::iter()
-        .flat_map(|face| {
-            CardSuit::iter().flat_map(move |suit| {
-                let num = match face {
-                    CardFace::One => 3,
-                    CardFace::Two | CardFace::Three | CardFace::Four => 2,
-                    CardFace::Five => 1,
-                };
-                vec![Card { suit, face }; num]
-            })
-        })
-        .collect();
-
-    return deck;
+    Variant::Standard.deck()
 }
 
-pub fn new_seeded_deck<R: SeedableRng + Rng>(seed: u64) -> Vec<Card> {
+pub fn new_seeded_deck<R: SeedableRng + Rng>(variant: Variant, seed: u64) -> Vec<Card> {
     let mut rand = R::seed_from_u64(seed);
 
-    let mut deck: Vec<Card> = CardFace::iter()
-        .flat_map(|face| {
-            CardSuit::iter().flat_map(move |suit| {
-                let num = match face {
-                    CardFace::One => 3,
-                    CardFace::Two | CardFace::Three | CardFace::Four => 2,
-                    CardFace::Five => 1,
-                };
-                vec![Card { suit, face }; num]
-            })
-        })
-        .collect();
+    let mut deck: Vec<Card> = variant.deck();
 
     for index in 0..deck.len() {
         let swap = rand.gen_range(index..deck.len());
@@ -504,6 +646,7 @@ mod tests {
     use rand::rngs::StdRng;
 
     use super::*;
+    use crate::model::{ClueEconomy, RuleValidation};
 
     use CardFace::*;
     use CardSuit::*;
@@ -515,7 +658,8 @@ mod tests {
     fn card_slot(face: CardFace, suit: CardSuit) -> Option<Slot> {
         Some(Slot {
             card: card(face, suit),
-            hints: vec![],
+            hints: HintKnowledge::new(),
+            hint_history: vec![],
             draw_number: 0,
         })
     }
@@ -526,7 +670,8 @@ mod tests {
             .map(|card| {
                 card.map(|card| Slot {
                     card,
-                    hints: vec![],
+                    hints: HintKnowledge::new(),
+                    hint_history: vec![],
                     draw_number: 0,
                 })
             })
@@ -555,6 +700,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_draw_numbers_follow_deck_size() {
+        let config = GameConfig::new(2, 0).with_variant(Variant::Black);
+        let game_state = GameState::start_with_seed::<StdRng>(&config).unwrap();
+
+        let draw_numbers = game_state
+            .players
+            .iter()
+            .flat_map(|p| p.hand.iter().flatten().map(|s| s.draw_number))
+            .sorted()
+            .collect_vec();
+        assert_eq!(draw_numbers, (0..10).collect_vec());
+        assert_eq!(game_state.draw_pile.len(), 55 - 10);
+
+        let short_deck = vec![card(One, Red); 12];
+        let game_state =
+            GameState::start_with_deck::<StdRng>(&GameConfig::new(2, 0), short_deck).unwrap();
+        let last_slot = game_state.players[1].hand[4].as_ref().unwrap();
+        assert_eq!(last_slot.draw_number, 9);
+    }
+
+    #[test]
+    fn test_rejects_undealable_config() {
+        assert_eq!(
+            GameState::start_with_seed::<StdRng>(&GameConfig::new(0, 0)),
+            Err(GameError::InvalidConfig)
+        );
+
+        let short_deck = vec![card(One, Red); 9];
+        assert_eq!(
+            GameState::start_with_deck::<StdRng>(&GameConfig::new(2, 0), short_deck),
+            Err(GameError::InvalidConfig)
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_effects_are_errors() {
+        let mut game_state = GameState {
+            draw_pile: vec![],
+            played_cards: PlayedStacks::new(),
+            discard_pile: vec![],
+            players: vec![
+                player(&[card_slot(One, Blue), card_slot(Five, Red)]),
+                player(&[card_slot(Four, Green), card_slot(Five, Green)]),
+            ],
+            remaining_bomb_count: 0,
+            remaining_hint_count: 0,
+            has_half_hint: false,
+            turn: 0,
+            last_turn: None,
+            outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
+        };
+
+        for effect in [
+            GameEffect::DrawCard(PlayerIndex(0), SlotIndex(0)),
+            GameEffect::RemoveCard(PlayerIndex(2), SlotIndex(0)),
+            GameEffect::HintCard(
+                PlayerIndex(0),
+                SlotIndex(5),
+                Hint::IsFace(One),
+                HintOrigin {
+                    giver: PlayerIndex(1),
+                    turn: 0,
+                },
+            ),
+            GameEffect::DecHint,
+            GameEffect::BurnFuse,
+            GameEffect::MoveSlot(PlayerIndex(1), SlotIndex(0), SlotIndex(2)),
+        ] {
+            assert_eq!(
+                game_state.run_effect(effect),
+                Err(GameError::InconsistentEffect(effect))
+            );
+        }
+
+        game_state
+            .run_effect(GameEffect::RemoveCard(PlayerIndex(0), SlotIndex(0)))
+            .unwrap();
+        let draw = GameEffect::DrawCard(PlayerIndex(0), SlotIndex(0));
+        assert_eq!(
+            game_state.run_effect(draw),
+            Err(GameError::InconsistentEffect(draw))
+        );
+    }
+
     #[test]
     fn test_game_state_start_4_players() {
         let game_state = GameState::start_with_seed::<StdRng>(&GameConfig::new(4, 0)).unwrap();
@@ -587,7 +819,7 @@ mod tests {
     fn test_remove_card_effect() {
         let mut game_state = GameState {
             draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[card_slot(Four, Blue), card_slot(Five, Blue)]),
@@ -595,9 +827,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 0,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         game_state
@@ -620,7 +855,7 @@ mod tests {
     fn test_add_to_discard_effect() {
         let mut game_state = GameState {
             draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[card_slot(Four, Blue), card_slot(Five, Blue)]),
@@ -628,9 +863,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 0,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         game_state
@@ -650,7 +888,7 @@ mod tests {
     fn test_draw_card_effect() {
         let mut game_state = GameState {
             draw_pile: vec![card(Two, Red), card(Three, Red), card(One, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[None, card_slot(Five, Blue)]),
@@ -658,15 +896,19 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 0,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         let expected_drawed_card_slot = Slot {
             card: card(One, Red),
-            hints: vec![],
-            draw_number: num_cards() - 3,
+            hints: HintKnowledge::new(),
+            hint_history: vec![],
+            draw_number: 3,
         };
 
         game_state
@@ -690,7 +932,7 @@ mod tests {
     fn test_dec_hint_effect() {
         let mut game_state = GameState {
             draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[None, card_slot(Five, Blue)]),
@@ -698,9 +940,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 0,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         game_state.run_effects(vec![GameEffect::DecHint]).unwrap();
@@ -718,17 +963,20 @@ mod tests {
     fn test_inc_hint_effect() {
         let mut game_state = GameState {
             draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[None, card_slot(Five, Blue)]),
                 player(&[card_slot(Four, Green), card_slot(Five, Green)]),
             ],
             remaining_bomb_count: 3,
-            remaining_hint_count: 8,
+            remaining_hint_count: 7,
+            has_half_hint: false,
             turn: 0,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         game_state.run_effects(vec![GameEffect::IncHint]).unwrap();
@@ -736,17 +984,88 @@ mod tests {
         assert_eq!(
             game_state,
             GameState {
-                remaining_hint_count: 9,
+                remaining_hint_count: 8,
+                ..game_state.clone()
+            }
+        );
+
+        // capped at the max hint count
+        game_state.run_effects(vec![GameEffect::IncHint]).unwrap();
+
+        assert_eq!(game_state.remaining_hint_count, 8);
+
+        // even when the cap is as high as a count goes
+        game_state.config.clue_economy.max_hints = u8::MAX;
+        game_state.remaining_hint_count = u8::MAX;
+        game_state.run_effects(vec![GameEffect::IncHint]).unwrap();
+
+        assert_eq!(game_state.remaining_hint_count, u8::MAX);
+    }
+
+    #[test]
+    fn test_inc_half_hint_effect() {
+        let mut game_state = GameState {
+            draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
+            played_cards: PlayedStacks::new(),
+            discard_pile: vec![],
+            players: vec![
+                player(&[None, card_slot(Five, Blue)]),
+                player(&[card_slot(Four, Green), card_slot(Five, Green)]),
+            ],
+            remaining_bomb_count: 3,
+            remaining_hint_count: 6,
+            has_half_hint: false,
+            turn: 0,
+            last_turn: None,
+            outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
+        };
+
+        game_state
+            .run_effects(vec![GameEffect::IncHalfHint])
+            .unwrap();
+
+        assert_eq!(
+            game_state,
+            GameState {
+                remaining_hint_count: 6,
+                has_half_hint: true,
+                ..game_state.clone()
+            }
+        );
+
+        game_state
+            .run_effects(vec![GameEffect::IncHalfHint])
+            .unwrap();
+
+        assert_eq!(
+            game_state,
+            GameState {
+                remaining_hint_count: 7,
+                has_half_hint: false,
                 ..game_state.clone()
             }
         );
+
+        // no half clue left over once the max is reached
+        game_state
+            .run_effects(vec![
+                GameEffect::IncHalfHint,
+                GameEffect::IncHalfHint,
+                GameEffect::IncHalfHint,
+            ])
+            .unwrap();
+
+        assert_eq!(game_state.remaining_hint_count, 8);
+        assert!(!game_state.has_half_hint);
     }
 
     #[test]
     fn test_burn_fuse_effect() {
         let mut game_state = GameState {
             draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[None, card_slot(Five, Blue)]),
@@ -754,9 +1073,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 0,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         game_state
@@ -776,7 +1098,7 @@ mod tests {
     fn test_place_on_board_effect() {
         let mut game_state = GameState {
             draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[None, card_slot(Five, Blue)]),
@@ -784,9 +1106,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 0,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         game_state
@@ -796,7 +1121,7 @@ mod tests {
         assert_eq!(
             game_state,
             GameState {
-                played_cards: vec![card(One, Yellow)],
+                played_cards: vec![card(One, Yellow)].into(),
                 ..game_state.clone()
             }
         );
@@ -806,7 +1131,7 @@ mod tests {
     fn test_mark_last_turn_effect() {
         let mut game_state = GameState {
             draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[None, card_slot(Five, Blue)]),
@@ -814,29 +1139,92 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         game_state
             .run_effects([GameEffect::MarkLastTurn(12)].to_vec())
             .unwrap();
 
+        assert_eq!(game_state.last_turn, Some(12));
         assert_eq!(
-            game_state,
-            GameState {
-                last_turn: Some(12),
-                ..game_state.clone()
-            }
+            game_state.status,
+            GameStatus::LastRound { turns_remaining: 3 }
+        );
+    }
+
+    #[test]
+    fn test_status_through_last_round() {
+        let short_deck = vec![card(One, Blue), card(Five, Red)]
+            .into_iter()
+            .chain(iter::repeat_n(card(Two, Green), 10))
+            .collect_vec();
+        let mut game_state =
+            GameState::start_with_deck::<StdRng>(&GameConfig::new(2, 0), short_deck).unwrap();
+        assert_eq!(game_state.status, GameStatus::Playing);
+
+        let turns_remaining = (0..4)
+            .map(|_| {
+                let effects = game_state
+                    .play(PlayerAction::DiscardCard(SlotIndex(0)))
+                    .unwrap();
+                game_state.run_effects(effects).unwrap();
+                game_state.turns_remaining()
+            })
+            .collect_vec();
+
+        // the second discard empties the draw pile, then each player gets one more turn
+        assert_eq!(turns_remaining, vec![None, Some(2), Some(1), None]);
+        assert_eq!(game_state.turn, 4);
+        assert_eq!(
+            game_state.status,
+            GameStatus::Finished(GameOutcome::Fail { score: 0 })
+        );
+        assert_matches!(
+            game_state.play(PlayerAction::DiscardCard(SlotIndex(0))),
+            Err(GameError::GameOver(_))
         );
     }
 
+    #[test]
+    fn test_deal_without_fuses_ends_the_game() {
+        let config = GameConfig {
+            num_fuses: 0,
+            ..GameConfig::new(2, 0)
+        };
+        let game_state = GameState::start_with_seed::<StdRng>(&config).unwrap();
+
+        assert_eq!(
+            game_state.status,
+            GameStatus::Finished(GameOutcome::Fail { score: 0 })
+        );
+        assert_eq!(game_state.last_turn, None);
+        assert_matches!(
+            game_state.play(PlayerAction::DiscardCard(SlotIndex(0))),
+            Err(GameError::GameOver(_))
+        );
+    }
+
+    #[test]
+    fn test_fully_dealt_deck_starts_last_round() {
+        let deck = vec![card(One, Red); 10];
+        let game_state =
+            GameState::start_with_deck::<StdRng>(&GameConfig::new(2, 0), deck).unwrap();
+
+        assert_eq!(game_state.last_turn, Some(1));
+        assert_eq!(game_state.turns_remaining(), Some(2));
+    }
+
     #[test]
     fn test_mark_next_turn_effect() {
         let mut game_state = GameState {
             draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[None, card_slot(Five, Blue)]),
@@ -844,9 +1232,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         game_state
@@ -866,7 +1257,7 @@ mod tests {
     fn test_mark_hint_effect() {
         let mut game_state = GameState {
             draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[None, card_slot(Five, Blue)]),
@@ -874,17 +1265,25 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
+        let origin = HintOrigin {
+            giver: PlayerIndex(0),
+            turn: 10,
+        };
         game_state
             .run_effects(
                 [GameEffect::HintCard(
                     PlayerIndex(1),
                     SlotIndex(1),
                     Hint::IsFace(Five),
+                    origin,
                 )]
                 .to_vec(),
             )
@@ -900,7 +1299,8 @@ mod tests {
                             card_slot(Four, Green),
                             Some(Slot {
                                 card: card(Five, Green),
-                                hints: [Hint::IsFace(Five)].to_vec(),
+                                hints: vec![Hint::IsFace(Five)].into(),
+                                hint_history: vec![(Hint::IsFace(Five), origin)],
                                 draw_number: 0,
                             })
                         ]
@@ -917,7 +1317,7 @@ mod tests {
     fn test_move_slot_effect() {
         let mut game_state = GameState {
             draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[card_slot(One, Blue), card_slot(Five, Blue)]),
@@ -925,9 +1325,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         game_state
@@ -971,7 +1374,7 @@ mod tests {
     fn test_plays_normal_card_action() {
         let game_state = GameState {
             draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[card_slot(One, Blue), card_slot(Five, Blue)]),
@@ -979,9 +1382,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 1,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         let effects = game_state.play(PlayerAction::PlayCard(SlotIndex(1)));
@@ -1001,7 +1407,7 @@ mod tests {
     fn test_plays_card_last_turn_action() {
         let game_state = GameState {
             draw_pile: vec![],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[card_slot(One, Blue), card_slot(Five, Blue)]),
@@ -1009,9 +1415,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 12,
             last_turn: Some(12),
             outcome: None,
+            status: GameStatus::LastRound { turns_remaining: 1 },
+            config: GameConfig::new(2, 0),
         };
 
         let effects = game_state.play(PlayerAction::PlayCard(SlotIndex(0)));
@@ -1030,7 +1439,7 @@ mod tests {
     fn test_plays_rejected_card_action() {
         let game_state = GameState {
             draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 player(&[card_slot(One, Blue), card_slot(Five, Blue)]),
@@ -1038,9 +1447,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 1,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         let effects = game_state.play(PlayerAction::PlayCard(SlotIndex(0)));
@@ -1066,7 +1478,8 @@ mod tests {
                 card(Two, Red),
                 card(Three, Red),
                 card(Four, Red),
-            ],
+            ]
+            .into(),
             discard_pile: vec![],
             players: vec![
                 player(&[card_slot(One, Blue), card_slot(Five, Red)]),
@@ -1074,9 +1487,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         let effects = game_state.play(PlayerAction::PlayCard(SlotIndex(1)));
@@ -1093,6 +1509,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hidden_completing_play_gives_no_hint() {
+        let game_state = GameState {
+            draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
+            played_cards: vec![
+                card(One, Red),
+                card(Two, Red),
+                card(Three, Red),
+                card(Four, Red),
+            ]
+            .into(),
+            discard_pile: vec![],
+            players: vec![
+                player(&[card_slot(One, Blue), card_slot(Five, Red)]),
+                player(&[card_slot(Four, Green), card_slot(One, Yellow)]),
+            ],
+            remaining_bomb_count: 3,
+            remaining_hint_count: 4,
+            has_half_hint: false,
+            turn: 10,
+            last_turn: None,
+            outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0).with_variant(Variant::ThrowItInAHole),
+        };
+
+        let effects = game_state.play(PlayerAction::PlayCard(SlotIndex(1)));
+
+        assert_vector_contains_eq(
+            effects.unwrap(),
+            vec![
+                GameEffect::RemoveCard(PlayerIndex(0), SlotIndex(1)),
+                GameEffect::PlaceOnBoard(card(Five, Red)),
+                GameEffect::DrawCard(PlayerIndex(0), SlotIndex(1)),
+                GameEffect::NextTurn(11),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_plays_completing_card_without_hint_action() {
+        let game_state = GameState {
+            draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
+            played_cards: vec![
+                card(One, Red),
+                card(Two, Red),
+                card(Three, Red),
+                card(Four, Red),
+            ]
+            .into(),
+            discard_pile: vec![],
+            players: vec![
+                player(&[card_slot(One, Blue), card_slot(Five, Red)]),
+                player(&[card_slot(Four, Green), card_slot(One, Yellow)]),
+            ],
+            remaining_bomb_count: 3,
+            remaining_hint_count: 5,
+            has_half_hint: false,
+            turn: 10,
+            last_turn: None,
+            outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0).with_clue_economy(ClueEconomy {
+                hint_on_completed_set: false,
+                ..ClueEconomy::default()
+            }),
+        };
+
+        let effects = game_state.play(PlayerAction::PlayCard(SlotIndex(1)));
+
+        assert_vector_contains_eq(
+            effects.unwrap(),
+            vec![
+                GameEffect::RemoveCard(PlayerIndex(0), SlotIndex(1)),
+                GameEffect::PlaceOnBoard(card(Five, Red)),
+                GameEffect::DrawCard(PlayerIndex(0), SlotIndex(1)),
+                GameEffect::NextTurn(11),
+            ],
+        );
+    }
+
     #[test]
     fn test_plays_completing_card_with_last_card_draw_action() {
         let game_state = GameState {
@@ -1102,7 +1599,8 @@ mod tests {
                 card(Two, Red),
                 card(Three, Red),
                 card(Four, Red),
-            ],
+            ]
+            .into(),
             discard_pile: vec![],
             players: vec![
                 player(&[card_slot(One, Blue), card_slot(Five, Red)]),
@@ -1110,9 +1608,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         let effects = game_state.play(PlayerAction::PlayCard(SlotIndex(1)));
@@ -1139,7 +1640,8 @@ mod tests {
                 card(Two, Red),
                 card(Three, Red),
                 card(Four, Red),
-            ],
+            ]
+            .into(),
             discard_pile: vec![],
             players: vec![
                 player(&[card_slot(One, Blue), card_slot(Five, Red)]),
@@ -1147,9 +1649,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         let effects = game_state.play(PlayerAction::DiscardCard(SlotIndex(1)));
@@ -1166,6 +1671,84 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_discards_card_clue_starved_action() {
+        let game_state = GameState {
+            draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
+            played_cards: PlayedStacks::new(),
+            discard_pile: vec![],
+            players: vec![
+                player(&[card_slot(One, Blue), card_slot(Five, Red)]),
+                player(&[card_slot(Four, Green), card_slot(One, Yellow)]),
+            ],
+            remaining_bomb_count: 3,
+            remaining_hint_count: 4,
+            has_half_hint: false,
+            turn: 10,
+            last_turn: None,
+            outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0).with_clue_economy(ClueEconomy {
+                half_hint_on_discard: true,
+                ..ClueEconomy::default()
+            }),
+        };
+
+        let effects = game_state.play(PlayerAction::DiscardCard(SlotIndex(1)));
+
+        assert_vector_contains_eq(
+            effects.unwrap(),
+            vec![
+                GameEffect::RemoveCard(PlayerIndex(0), SlotIndex(1)),
+                GameEffect::AddToDiscard(card(Five, Red)),
+                GameEffect::DrawCard(PlayerIndex(0), SlotIndex(1)),
+                GameEffect::NextTurn(11),
+                GameEffect::IncHalfHint,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_ends_when_perfect_lost() {
+        let mut game_state = GameState {
+            draw_pile: vec![card(One, Red), card(Two, Red), card(Three, Red)],
+            played_cards: PlayedStacks::new(),
+            discard_pile: vec![],
+            players: vec![
+                player(&[card_slot(One, Blue), card_slot(Five, Red)]),
+                player(&[card_slot(Four, Green), card_slot(One, Yellow)]),
+            ],
+            remaining_bomb_count: 3,
+            remaining_hint_count: 4,
+            has_half_hint: false,
+            turn: 10,
+            last_turn: None,
+            outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
+        };
+        assert_eq!(game_state.max_achievable_score(), 25);
+        assert_eq!(game_state.pace(), 3 + 2 - 25);
+
+        let mut ending_early = GameState {
+            config: GameConfig::new(2, 0).with_end_when_perfect_lost(true),
+            ..game_state.clone()
+        };
+        for game_state in [&mut game_state, &mut ending_early] {
+            let effects = game_state
+                .play(PlayerAction::DiscardCard(SlotIndex(1)))
+                .unwrap();
+            game_state.run_effects(effects).unwrap();
+            assert_eq!(game_state.max_achievable_score(), 24);
+        }
+
+        assert_eq!(game_state.status, GameStatus::Playing);
+        assert_eq!(
+            ending_early.status,
+            GameStatus::Finished(GameOutcome::Fail { score: 0 })
+        );
+    }
+
     #[test]
     fn test_discards_card_no_draw_action() {
         let game_state = GameState {
@@ -1175,7 +1758,8 @@ mod tests {
                 card(Two, Red),
                 card(Three, Red),
                 card(Four, Red),
-            ],
+            ]
+            .into(),
             discard_pile: vec![],
             players: vec![
                 player(&[card_slot(One, Blue), card_slot(Five, Red)]),
@@ -1183,9 +1767,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: Some(12),
             outcome: None,
+            status: GameStatus::LastRound { turns_remaining: 3 },
+            config: GameConfig::new(2, 0),
         };
 
         let effects = game_state.play(PlayerAction::DiscardCard(SlotIndex(1)));
@@ -1210,7 +1797,8 @@ mod tests {
                 card(Two, Red),
                 card(Three, Red),
                 card(Four, Red),
-            ],
+            ]
+            .into(),
             discard_pile: vec![],
             players: vec![
                 player(&[card_slot(One, Blue), card_slot(Five, Red)]),
@@ -1218,9 +1806,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         let effects = game_state.play(PlayerAction::DiscardCard(SlotIndex(1)));
@@ -1247,7 +1838,8 @@ mod tests {
                 card(Two, Red),
                 card(Three, Red),
                 card(Four, Red),
-            ],
+            ]
+            .into(),
             discard_pile: vec![],
             players: vec![
                 player(&[
@@ -1265,9 +1857,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: Some(12),
             outcome: None,
+            status: GameStatus::LastRound { turns_remaining: 3 },
+            config: GameConfig::new(2, 0),
         };
 
         let effects = game_state.play(PlayerAction::GiveHint(
@@ -1275,13 +1870,17 @@ mod tests {
             HintAction::SameFace(Five),
         ));
 
+        let origin = HintOrigin {
+            giver: PlayerIndex(0),
+            turn: 10,
+        };
         assert_vector_contains_eq(
             effects.unwrap(),
             vec![
-                GameEffect::HintCard(PlayerIndex(1), SlotIndex(0), Hint::IsNotFace(Five)),
-                GameEffect::HintCard(PlayerIndex(1), SlotIndex(1), Hint::IsFace(Five)),
-                GameEffect::HintCard(PlayerIndex(1), SlotIndex(2), Hint::IsFace(Five)),
-                GameEffect::HintCard(PlayerIndex(1), SlotIndex(3), Hint::IsNotFace(Five)),
+                GameEffect::HintCard(PlayerIndex(1), SlotIndex(0), Hint::IsNotFace(Five), origin),
+                GameEffect::HintCard(PlayerIndex(1), SlotIndex(1), Hint::IsFace(Five), origin),
+                GameEffect::HintCard(PlayerIndex(1), SlotIndex(2), Hint::IsFace(Five), origin),
+                GameEffect::HintCard(PlayerIndex(1), SlotIndex(3), Hint::IsNotFace(Five), origin),
                 GameEffect::NextTurn(11),
                 GameEffect::DecHint,
             ],
@@ -1297,7 +1896,8 @@ mod tests {
                 card(Two, Red),
                 card(Three, Red),
                 card(Four, Red),
-            ],
+            ]
+            .into(),
             discard_pile: vec![],
             players: vec![
                 player(&[
@@ -1315,9 +1915,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: Some(12),
             outcome: None,
+            status: GameStatus::LastRound { turns_remaining: 3 },
+            config: GameConfig::new(2, 0),
         };
 
         let effects = game_state.play(PlayerAction::GiveHint(
@@ -1325,13 +1928,17 @@ mod tests {
             HintAction::SameSuit(Green),
         ));
 
+        let origin = HintOrigin {
+            giver: PlayerIndex(0),
+            turn: 10,
+        };
         assert_vector_contains_eq(
             effects.unwrap(),
             vec![
-                GameEffect::HintCard(PlayerIndex(1), SlotIndex(0), Hint::IsSuit(Green)),
-                GameEffect::HintCard(PlayerIndex(1), SlotIndex(1), Hint::IsNotSuit(Green)),
-                GameEffect::HintCard(PlayerIndex(1), SlotIndex(2), Hint::IsSuit(Green)),
-                GameEffect::HintCard(PlayerIndex(1), SlotIndex(3), Hint::IsNotSuit(Green)),
+                GameEffect::HintCard(PlayerIndex(1), SlotIndex(0), Hint::IsSuit(Green), origin),
+                GameEffect::HintCard(PlayerIndex(1), SlotIndex(1), Hint::IsNotSuit(Green), origin),
+                GameEffect::HintCard(PlayerIndex(1), SlotIndex(2), Hint::IsSuit(Green), origin),
+                GameEffect::HintCard(PlayerIndex(1), SlotIndex(3), Hint::IsNotSuit(Green), origin),
                 GameEffect::NextTurn(11),
                 GameEffect::DecHint,
             ],
@@ -1339,15 +1946,243 @@ mod tests {
     }
 
     #[test]
-    fn test_move_slot_action() {
+    fn test_gives_suit_hint_touching_rainbow_action() {
         let game_state = GameState {
             draw_pile: vec![],
+            played_cards: PlayedStacks::new(),
+            discard_pile: vec![],
+            players: vec![
+                player(&[card_slot(One, Blue), card_slot(Five, Red)]),
+                player(&[
+                    card_slot(Four, Green),
+                    card_slot(Five, Rainbow),
+                    card_slot(Two, Red),
+                ]),
+            ],
+            remaining_bomb_count: 3,
+            remaining_hint_count: 8,
+            has_half_hint: false,
+            turn: 10,
+            last_turn: None,
+            outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0).with_variant(Variant::Rainbow),
+        };
+
+        let effects = game_state.play(PlayerAction::GiveHint(
+            PlayerIndex(1),
+            HintAction::SameSuit(Green),
+        ));
+
+        let origin = HintOrigin {
+            giver: PlayerIndex(0),
+            turn: 10,
+        };
+        assert_vector_contains_eq(
+            effects.unwrap(),
+            vec![
+                GameEffect::HintCard(PlayerIndex(1), SlotIndex(0), Hint::IsSuit(Green), origin),
+                GameEffect::HintCard(PlayerIndex(1), SlotIndex(1), Hint::IsSuit(Green), origin),
+                GameEffect::HintCard(PlayerIndex(1), SlotIndex(2), Hint::IsNotSuit(Green), origin),
+                GameEffect::NextTurn(11),
+                GameEffect::DecHint,
+            ],
+        );
+
+        let rainbow_hint = game_state.play(PlayerAction::GiveHint(
+            PlayerIndex(1),
+            HintAction::SameSuit(Rainbow),
+        ));
+
+        assert!(rainbow_hint.is_err());
+    }
+
+    #[test]
+    fn test_rejects_hint_to_self_action() {
+        let game_state = GameState {
+            draw_pile: vec![card(One, Red), card(Two, Red)],
+            played_cards: vec![
+                card(One, Red),
+                card(Two, Red),
+                card(Three, Red),
+                card(Four, Red),
+            ]
+            .into(),
+            discard_pile: vec![],
+            players: vec![
+                player(&[card_slot(One, Blue), card_slot(Five, Red)]),
+                player(&[card_slot(Four, Green), card_slot(Five, Green)]),
+            ],
+            remaining_bomb_count: 3,
+            remaining_hint_count: 5,
+            has_half_hint: false,
+            turn: 10,
+            last_turn: None,
+            outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0).with_rules(RuleValidation::Strict),
+        };
+
+        let hint_to_self = PlayerAction::GiveHint(PlayerIndex(0), HintAction::SameFace(One));
+
+        assert_eq!(
+            game_state.play(hint_to_self),
+            Err(GameError::RuleViolation(RuleViolation::HintToSelf))
+        );
+
+        // lenient rules keep accepting it
+        let lenient_game_state = GameState {
+            config: GameConfig::new(2, 0),
+            ..game_state.clone()
+        };
+        assert!(lenient_game_state.play(hint_to_self).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_hint_touching_no_cards_action() {
+        let game_state = GameState {
+            draw_pile: vec![card(One, Red), card(Two, Red)],
+            played_cards: vec![
+                card(One, Red),
+                card(Two, Red),
+                card(Three, Red),
+                card(Four, Red),
+            ]
+            .into(),
+            discard_pile: vec![],
+            players: vec![
+                player(&[card_slot(One, Blue), card_slot(Five, Red)]),
+                player(&[card_slot(Four, Green), card_slot(Five, Green)]),
+            ],
+            remaining_bomb_count: 3,
+            remaining_hint_count: 5,
+            has_half_hint: false,
+            turn: 10,
+            last_turn: None,
+            outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0).with_rules(RuleValidation::Strict),
+        };
+
+        assert_eq!(
+            game_state.play(PlayerAction::GiveHint(
+                PlayerIndex(1),
+                HintAction::SameSuit(Yellow)
+            )),
+            Err(GameError::RuleViolation(RuleViolation::HintTouchesNoCards))
+        );
+        assert_eq!(
+            game_state.play(PlayerAction::GiveHint(
+                PlayerIndex(1),
+                HintAction::SameFace(Two)
+            )),
+            Err(GameError::RuleViolation(RuleViolation::HintTouchesNoCards))
+        );
+        assert!(game_state
+            .play(PlayerAction::GiveHint(
+                PlayerIndex(1),
+                HintAction::SameSuit(Green)
+            ))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rejects_discard_at_max_hints_action() {
+        let game_state = GameState {
+            draw_pile: vec![card(One, Red), card(Two, Red)],
+            played_cards: vec![
+                card(One, Red),
+                card(Two, Red),
+                card(Three, Red),
+                card(Four, Red),
+            ]
+            .into(),
+            discard_pile: vec![],
+            players: vec![
+                player(&[card_slot(One, Blue), card_slot(Five, Red)]),
+                player(&[card_slot(Four, Green), card_slot(Five, Green)]),
+            ],
+            remaining_bomb_count: 3,
+            remaining_hint_count: 8,
+            has_half_hint: false,
+            turn: 10,
+            last_turn: None,
+            outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0).with_rules(RuleValidation::Strict),
+        };
+
+        assert_eq!(
+            game_state.play(PlayerAction::DiscardCard(SlotIndex(0))),
+            Err(GameError::RuleViolation(RuleViolation::DiscardAtMaxHints))
+        );
+
+        let game_state = GameState {
+            remaining_hint_count: 7,
+            ..game_state
+        };
+        assert!(game_state
+            .play(PlayerAction::DiscardCard(SlotIndex(0)))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rejects_inc_hint_past_max_effect() {
+        let mut game_state = GameState {
+            draw_pile: vec![card(One, Red), card(Two, Red)],
             played_cards: vec![
                 card(One, Red),
                 card(Two, Red),
                 card(Three, Red),
                 card(Four, Red),
+            ]
+            .into(),
+            discard_pile: vec![],
+            players: vec![
+                player(&[card_slot(One, Blue), card_slot(Five, Red)]),
+                player(&[card_slot(Four, Green), card_slot(Five, Green)]),
             ],
+            remaining_bomb_count: 3,
+            remaining_hint_count: 8,
+            has_half_hint: false,
+            turn: 10,
+            last_turn: None,
+            outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0).with_rules(RuleValidation::Strict),
+        };
+
+        assert_eq!(
+            game_state.run_effect(GameEffect::IncHint),
+            Err(GameError::RuleViolation(RuleViolation::HintsAboveMax))
+        );
+        assert_eq!(game_state.remaining_hint_count, 8);
+
+        // completing a set at the max hint count loses the clue instead
+        let effects = game_state.play(PlayerAction::PlayCard(SlotIndex(1)));
+
+        assert_vector_contains_eq(
+            effects.unwrap(),
+            vec![
+                GameEffect::RemoveCard(PlayerIndex(0), SlotIndex(1)),
+                GameEffect::PlaceOnBoard(card(Five, Red)),
+                GameEffect::DrawCard(PlayerIndex(0), SlotIndex(1)),
+                GameEffect::NextTurn(11),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_move_slot_action() {
+        let game_state = GameState {
+            draw_pile: vec![],
+            played_cards: vec![
+                card(One, Red),
+                card(Two, Red),
+                card(Three, Red),
+                card(Four, Red),
+            ]
+            .into(),
             discard_pile: vec![],
             players: vec![
                 player(&[
@@ -1365,9 +2200,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: Some(12),
             outcome: None,
+            status: GameStatus::LastRound { turns_remaining: 3 },
+            config: GameConfig::new(2, 0),
         };
 
         let effects = game_state.play(PlayerAction::MoveSlot(
@@ -1390,19 +2228,21 @@ mod tests {
     fn test_drawing_last_card() {
         let mut game_state = GameState {
             draw_pile: vec![card(One, Green)],
-            played_cards: vec![],
+            played_cards: PlayedStacks::new(),
             discard_pile: vec![],
             players: vec![
                 Player {
                     hand: vec![
                         Some(Slot {
                             card: card(One, Red),
-                            hints: vec![],
+                            hints: HintKnowledge::new(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(Slot {
                             card: card(Two, Red),
-                            hints: vec![],
+                            hints: HintKnowledge::new(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                     ],
@@ -1411,12 +2251,14 @@ mod tests {
                     hand: vec![
                         Some(Slot {
                             card: card(One, Blue),
-                            hints: vec![],
+                            hints: HintKnowledge::new(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                         Some(Slot {
                             card: card(Two, Blue),
-                            hints: vec![],
+                            hints: HintKnowledge::new(),
+                            hint_history: vec![],
                             draw_number: 0,
                         }),
                     ],
@@ -1424,9 +2266,12 @@ mod tests {
             ],
             remaining_bomb_count: 3,
             remaining_hint_count: 8,
+            has_half_hint: false,
             turn: 10,
             last_turn: None,
             outcome: None,
+            status: GameStatus::Playing,
+            config: GameConfig::new(2, 0),
         };
 
         let action = PlayerAction::PlayCard(SlotIndex(0));
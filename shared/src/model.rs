@@ -1,4 +1,4 @@
-use enum_map::Enum;
+use enum_map::{Enum, EnumMap};
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
@@ -11,6 +11,8 @@ pub enum CardFace {
     Three,
     Four,
     Five,
+    /// Can start an empty stack in the Up or Down variant, never touched by a face hint
+    Start,
 }
 
 #[derive(
@@ -22,8 +24,12 @@ pub enum CardSuit {
     Yellow,
     White,
     Blue,
+    /// Touched by every color hint, but can't be named by one
+    Rainbow,
+    /// Dark suit with a single copy of each face, every black card is critical
+    Black,
 }
-#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct PlayerIndex(pub usize);
 
 #[derive(Serialize, Deserialize, Debug, Copy, Clone)]
@@ -32,7 +38,19 @@ pub struct FromPlayerIndex(pub usize);
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Copy, Clone)]
 pub struct SlotIndex(pub usize);
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Rule variants the engine knows how to play. The suit list, deck composition,
+/// hint matching and play acceptance for each variant live in `variant.rs`.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, Default, EnumIter)]
+pub enum Variant {
+    #[default]
+    Standard,
+    Rainbow,
+    Black,
+    UpOrDown,
+    ThrowItInAHole,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct GameConfig {
     pub num_players: usize,
     pub hand_size: usize,
@@ -40,6 +58,79 @@ pub struct GameConfig {
     pub num_hints: u8,
     pub starting_player: PlayerIndex,
     pub seed: u64,
+    #[serde(default)]
+    pub variant: Variant,
+    #[serde(default)]
+    pub clue_economy: ClueEconomy,
+    #[serde(default)]
+    pub rules: RuleValidation,
+    /// End the game as soon as a perfect score is out of reach
+    #[serde(default)]
+    pub end_when_perfect_lost: bool,
+}
+
+/// How clues are won back during a game
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ClueEconomy {
+    /// Clues can't be won back past this count
+    pub max_hints: u8,
+    /// Clue starved: a discard only gives back half a clue
+    pub half_hint_on_discard: bool,
+    /// Completing a stack gives back a clue
+    pub hint_on_completed_set: bool,
+}
+
+/// How strictly actions are checked against the rules, see `rules.rs`
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum RuleValidation {
+    /// Only what the engine needs to stay consistent
+    #[default]
+    Lenient,
+    /// The standard Hanabi rules
+    Strict,
+}
+
+/// Why an action (or one of its effects) breaks the strict rules
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RuleViolation {
+    HintToSelf,
+    HintTouchesNoCards,
+    DiscardAtMaxHints,
+    HintsAboveMax,
+}
+
+/// Why the engine refused an action, or couldn't apply one of its effects
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GameError {
+    GameOver(GameOutcome),
+    NotYourTurn {
+        current_player: PlayerIndex,
+    },
+    /// Cards can only be moved around in one's own hand
+    NotYourHand,
+    /// No players, not enough cards in the deck to deal every hand, or not a bot
+    /// for each player
+    InvalidConfig,
+    InvalidPlayer(PlayerIndex),
+    /// Out of the hand, or no card in that slot
+    InvalidSlot(SlotIndex),
+    CannotMoveToSameSlot,
+    NoHintsLeft,
+    /// The hint can't be given in the game's variant
+    HintNotAllowed(HintAction),
+    RuleViolation(RuleViolation),
+    /// The effect doesn't apply to the current state, e.g. a corrupt replay
+    InconsistentEffect(GameEffect),
+}
+
+impl Default for ClueEconomy {
+    fn default() -> Self {
+        Self {
+            max_hints: 8,
+            half_hint_on_discard: false,
+            hint_on_completed_set: true,
+        }
+    }
 }
 
 impl GameConfig {
@@ -55,31 +146,66 @@ impl GameConfig {
             num_hints: 8,
             starting_player: PlayerIndex(0),
             seed,
+            variant: Variant::Standard,
+            clue_economy: ClueEconomy::default(),
+            rules: RuleValidation::Lenient,
+            end_when_perfect_lost: false,
+        }
+    }
+
+    pub fn with_variant(self, variant: Variant) -> Self {
+        Self { variant, ..self }
+    }
+
+    pub fn with_clue_economy(self, clue_economy: ClueEconomy) -> Self {
+        Self {
+            clue_economy,
+            ..self
+        }
+    }
+
+    pub fn with_rules(self, rules: RuleValidation) -> Self {
+        Self { rules, ..self }
+    }
+
+    pub fn with_end_when_perfect_lost(self, end_when_perfect_lost: bool) -> Self {
+        Self {
+            end_when_perfect_lost,
+            ..self
         }
     }
 }
 
-// TODO Maybe use something like this for clarity
-// #[derive(Serialize, Deserialize, Debug, Clone)]
-// pub enum GameStatus {
-//     WaitingToStart,
-//     Playing { turn_count: usize },
-//     LastRound { turns_remaining: usize },
-//     Finished(GameOutcome),
-// }
+/// Where the game is at, kept up to date by the engine as effects are run
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum GameStatus {
+    /// Hands are still being dealt
+    #[default]
+    WaitingToStart,
+    Playing,
+    /// The draw pile is empty, the count includes the turn being played
+    LastRound {
+        turns_remaining: u8,
+    },
+    Finished(GameOutcome),
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct GameState {
     pub draw_pile: Vec<Card>, // TODO: maybe convert to a board with a draw pile and discard pile and organized sets
-    pub played_cards: Vec<Card>, // TODO: organize by suit sets
+    pub played_cards: PlayedStacks,
     pub discard_pile: Vec<Card>,
     pub players: Vec<Player>,
     pub remaining_bomb_count: u8,
     pub remaining_hint_count: u8,
+    /// Half a clue won back by a discard in clue starved games
+    #[serde(default)]
+    pub has_half_hint: bool,
     pub turn: u8,              // todo maybe convert to player index
     pub last_turn: Option<u8>, // we end there
     pub outcome: Option<GameOutcome>,
-    // pub status: GameStatus,
+    pub config: GameConfig,
+    pub status: GameStatus,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -96,15 +222,24 @@ pub struct GameSnapshotEvent {
 pub struct GameStateSnapshot {
     pub this_client_player_index: PlayerIndex,
     pub draw_pile_count: u8, // TODO: maybe convert to a board with a draw pile and discard pile and organized sets
-    pub played_cards: Vec<Card>, // TODO: organize by suit sets
+    pub played_cards: PlayedStacks,
     pub discard_pile: Vec<Card>,
     pub players: Vec<ClientPlayerView>,
     pub remaining_bomb_count: u8,
     pub remaining_hint_count: u8,
+    #[serde(default)]
+    pub has_half_hint: bool,
     pub current_turn_player_index: PlayerIndex,
     pub num_rounds: u8,        // todo maybe convert to player index
     pub last_turn: Option<u8>, // we end there
     pub outcome: Option<GameOutcome>,
+    #[serde(default)]
+    pub status: GameStatus,
+    /// Plays this client can't see the card of (Throw It In A Hole), those cards
+    /// are left out of `played_cards` and `discard_pile`. The fuses they burnt
+    /// still count in `remaining_bomb_count`
+    #[serde(default)]
+    pub cards_in_hole: usize,
 
     pub game_config: GameConfig,
     // TODO
@@ -114,9 +249,17 @@ pub struct GameStateSnapshot {
     // Status (waiting to start, playing)
 }
 
+/// Cards on the board, one stack of faces per suit in the order they were played.
+/// Serialized as the flat list of played cards, see `stacks.rs`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(from = "Vec<Card>", into = "Vec<Card>")]
+pub struct PlayedStacks {
+    pub(crate) stacks: EnumMap<CardSuit, Vec<CardFace>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ClientVisibleCard {
-    pub hints: Vec<Hint>,
+    pub hints: HintKnowledge,
     pub card: Card,
 }
 
@@ -139,7 +282,7 @@ pub struct Card {
     // hints: Vec<Hint>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum HintAction {
     SameSuit(CardSuit),
     SameFace(CardFace),
@@ -153,6 +296,19 @@ pub enum PlayerAction {
     MoveSlot(PlayerIndex, SlotIndex, SlotIndex),
 }
 
+/// How much a card matters for the rest of the game, see `Variant::classify_card`
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CardClass {
+    /// Can be played right now
+    Playable,
+    /// Already played, or out of reach because a card it builds on is lost
+    Trash,
+    /// The last copy left, losing it costs points
+    Critical,
+    /// Needed later, and other copies are still around
+    Normal,
+}
+
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 pub enum PlayedCardResult {
     Accepted,
@@ -160,15 +316,26 @@ pub enum PlayedCardResult {
     Rejected,
 }
 
+/// Which way a suit's stack is being built
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StackDirection {
+    /// Nothing (or only a start card) played yet, so both ways are still open
+    Undecided,
+    Up,
+    Down,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GameEffect {
     DrawCard(PlayerIndex, SlotIndex),
     RemoveCard(PlayerIndex, SlotIndex),
     AddToDiscard(Card),
     PlaceOnBoard(Card),
-    HintCard(PlayerIndex, SlotIndex, Hint),
+    HintCard(PlayerIndex, SlotIndex, Hint, HintOrigin),
     DecHint,
     IncHint,
+    /// Half a clue back, two halves make a whole clue
+    IncHalfHint,
     BurnFuse,
     NextTurn(u8),
     MarkLastTurn(u8),
@@ -176,6 +343,13 @@ pub enum GameEffect {
     MoveSlot(PlayerIndex, SlotIndex, SlotIndex),
 }
 
+/// Who gave a hint, and on which turn
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct HintOrigin {
+    pub giver: PlayerIndex,
+    pub turn: u8,
+}
+
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Hint {
     IsSuit(CardSuit),
@@ -184,16 +358,42 @@ pub enum Hint {
     IsNotFace(CardFace),
 }
 
+/// What the hints a card received say about it: the cards it can still be, one bit
+/// per suit and face, and which suits and faces it was hinted as or not.
+/// Also deserializes from the plain list of hints older games stored, see `hints.rs`
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "crate::hints::StoredHints")]
+pub struct HintKnowledge {
+    pub(crate) possible: u64,
+    pub(crate) suits: u8,
+    pub(crate) faces: u8,
+    pub(crate) not_suits: u8,
+    pub(crate) not_faces: u8,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Slot {
     pub card: Card,
-    pub hints: Vec<Hint>,
+    pub hints: HintKnowledge,
+    /// Every hint the card received, oldest first
+    #[serde(default)]
+    pub hint_history: Vec<(Hint, HintOrigin)>,
     pub draw_number: usize,
 }
 
+/// The cards a slot can still hold from one player's point of view, each with
+/// the number of its copies that player can't see, see `knowledge.rs`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CardKnowledge {
+    pub possible_cards: Vec<(Card, usize)>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct HiddenSlot {
-    pub hints: Vec<Hint>,
+    pub hints: HintKnowledge,
+    /// Every hint the card received, oldest first
+    #[serde(default)]
+    pub hint_history: Vec<(Hint, HintOrigin)>,
     pub draw_number: usize,
 }
 
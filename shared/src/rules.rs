@@ -0,0 +1,106 @@
+use std::fmt;
+
+use crate::model::{
+    Card, GameConfig, GameEffect, GameState, HintAction, PlayerAction, PlayerIndex, RuleValidation,
+    RuleViolation, Variant,
+};
+
+impl fmt::Display for RuleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            RuleViolation::HintToSelf => "You can't give a hint to yourself",
+            RuleViolation::HintTouchesNoCards => "A hint has to touch at least one card",
+            RuleViolation::DiscardAtMaxHints => {
+                "You can't discard while all the hints are available"
+            }
+            RuleViolation::HintsAboveMax => "The hint count can't go above the maximum",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
+// The rules come down to these checks, shared by the engine and the clients listing
+// what a player can do from what they see
+
+/// Giving a hint spends one
+pub(crate) fn can_hint(remaining_hint_count: u8) -> bool {
+    remaining_hint_count > 0
+}
+
+/// The strict rules don't let a player discard while all the hints are available
+pub(crate) fn can_discard(config: &GameConfig, remaining_hint_count: u8) -> bool {
+    config.rules == RuleValidation::Lenient || !is_at_max_hints(config, remaining_hint_count)
+}
+
+/// The strict rules don't let a hint be given when it touches none of the cards
+pub(crate) fn hint_touches_any(
+    variant: Variant,
+    hint: &HintAction,
+    mut cards: impl Iterator<Item = Card>,
+) -> bool {
+    cards.any(|card| variant.hint_touches(&card, hint))
+}
+
+fn is_at_max_hints(config: &GameConfig, remaining_hint_count: u8) -> bool {
+    remaining_hint_count >= config.clue_economy.max_hints
+}
+
+impl GameState {
+    fn is_strict(&self) -> bool {
+        self.config.rules == RuleValidation::Strict
+    }
+
+    /// Checks an action of the current player against the strict rules.
+    /// Always passes for games played with lenient rules.
+    pub fn validate_action(&self, action: &PlayerAction) -> Result<(), RuleViolation> {
+        if !self.is_strict() {
+            return Ok(());
+        }
+
+        match action {
+            PlayerAction::GiveHint(hinted_player_index, _)
+                if *hinted_player_index == self.current_player_index() =>
+            {
+                Err(RuleViolation::HintToSelf)
+            }
+            PlayerAction::GiveHint(PlayerIndex(hinted_player_index), hint) => {
+                let touches_any_card = self.players.get(*hinted_player_index).is_none_or(|p| {
+                    hint_touches_any(
+                        self.config.variant,
+                        hint,
+                        p.hand.iter().flatten().map(|slot| slot.card),
+                    )
+                });
+
+                if touches_any_card {
+                    Ok(())
+                } else {
+                    Err(RuleViolation::HintTouchesNoCards)
+                }
+            }
+            PlayerAction::DiscardCard(_)
+                if !can_discard(&self.config, self.remaining_hint_count) =>
+            {
+                Err(RuleViolation::DiscardAtMaxHints)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks an effect against the strict rules before it is applied.
+    /// Always passes for games played with lenient rules.
+    pub fn validate_effect(&self, effect: &GameEffect) -> Result<(), RuleViolation> {
+        if !self.is_strict() {
+            return Ok(());
+        }
+
+        match effect {
+            GameEffect::IncHint | GameEffect::IncHalfHint
+                if is_at_max_hints(&self.config, self.remaining_hint_count) =>
+            {
+                Err(RuleViolation::HintsAboveMax)
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use std::iter;
+
+use itertools::{Either, Itertools};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::model::{
+    Card, ClientPlayerView, GameState, GameStateSnapshot, HiddenSlot, HintKnowledge, Player, Slot,
+};
+
+/// The deals of a player's hidden hand, counted once to be sampled many times
+struct Deals<'a> {
+    snapshot: &'a GameStateSnapshot,
+    hand: &'a [Option<HiddenSlot>],
+    /// Each card left unseen once, and how many copies of it
+    cards: Vec<Card>,
+    copies: Vec<u8>,
+    /// Slots with hints, the others take whatever is left, whatever card it is
+    hinted: Vec<usize>,
+    free: Vec<usize>,
+    /// Deals of the hinted slots from an index on, by the copies left
+    counts: HashMap<(usize, Vec<u8>), u64>,
+}
+
+impl<'a> Deals<'a> {
+    fn new(snapshot: &'a GameStateSnapshot) -> Option<Self> {
+        let Some(ClientPlayerView::Me { hand, .. }) =
+            snapshot.players.get(snapshot.this_client_player_index.0)
+        else {
+            return None;
+        };
+        if snapshot.cards_in_hole > 0 {
+            return None;
+        }
+
+        let unseen = snapshot.unseen_cards();
+        let cards = unseen.iter().copied().unique().collect_vec();
+        let copies = cards
+            .iter()
+            .map(|card| unseen.iter().filter(|c| *c == card).count() as u8)
+            .collect_vec();
+        let (hinted, free) = hand
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| Some((index, slot.as_ref()?)))
+            .partition_map(|(index, slot)| {
+                if slot.hints == HintKnowledge::new() {
+                    Either::Right(index)
+                } else {
+                    Either::Left(index)
+                }
+            });
+
+        Some(Deals {
+            snapshot,
+            hand,
+            cards,
+            copies,
+            hinted,
+            free,
+            counts: HashMap::new(),
+        })
+    }
+
+    fn hints(&self, index: usize) -> &HintKnowledge {
+        &self.hand[self.hinted[index]]
+            .as_ref()
+            .expect("only filled slots are dealt")
+            .hints
+    }
+
+    /// Ways to deal the hinted slots from `index` on, one card at a time, from the
+    /// copies left of each card
+    fn count(&mut self, copies: &mut [u8], index: usize) -> u64 {
+        if index == self.hinted.len() {
+            return 1;
+        }
+        if let Some(count) = self.counts.get(&(index, copies.to_vec())) {
+            return *count;
+        }
+
+        let count = (0..self.cards.len())
+            .map(|card| self.count_with(copies, index, card))
+            .sum();
+        self.counts.insert((index, copies.to_vec()), count);
+        count
+    }
+
+    /// Ways to deal the hinted slots from `index` on, giving this card to the first
+    fn count_with(&mut self, copies: &mut [u8], index: usize, card: usize) -> u64 {
+        if copies[card] == 0 || !self.hints(index).is_possible(&self.cards[card]) {
+            return 0;
+        }
+        let ways = copies[card] as u64;
+        copies[card] -= 1;
+        let count = ways * self.count(copies, index + 1);
+        copies[card] += 1;
+        count
+    }
+
+    fn sample<R: Rng>(&mut self, rng: &mut R) -> Option<GameState> {
+        let mut copies = self.copies.clone();
+        let mut dealt: Vec<Option<Slot>> = vec![None; self.hand.len()];
+        let hand = self.hand;
+        let deal = |slot_index: usize, card| {
+            let slot = hand[slot_index]
+                .as_ref()
+                .expect("only filled slots are dealt");
+            Some(Slot {
+                card,
+                hints: slot.hints,
+                hint_history: slot.hint_history.clone(),
+                draw_number: slot.draw_number,
+            })
+        };
+
+        // each card is picked as often as the deals of the next slots it leaves
+        for index in 0..self.hinted.len() {
+            let weights = (0..self.cards.len())
+                .map(|card| self.count_with(&mut copies, index, card))
+                .collect_vec();
+            let mut pick = rng.gen_range(0..weights.iter().sum::<u64>().max(1));
+            let card = weights.iter().position(|&weight| {
+                if pick < weight {
+                    return true;
+                }
+                pick -= weight;
+                false
+            })?;
+
+            copies[card] -= 1;
+            dealt[self.hinted[index]] = deal(self.hinted[index], self.cards[card]);
+        }
+
+        let mut rest = self
+            .cards
+            .iter()
+            .zip(&copies)
+            .flat_map(|(card, &count)| iter::repeat_n(*card, count as usize))
+            .collect_vec();
+        rest.shuffle(rng);
+        for &slot_index in &self.free {
+            dealt[slot_index] = deal(slot_index, rest.pop()?);
+        }
+        if rest.len() != self.snapshot.draw_pile_count as usize {
+            return None;
+        }
+
+        let snapshot = self.snapshot;
+        let players = snapshot
+            .players
+            .iter()
+            .map(|player| match player {
+                ClientPlayerView::Me { .. } => Player {
+                    hand: dealt.clone(),
+                },
+                ClientPlayerView::Teammate { hand, .. } => Player { hand: hand.clone() },
+            })
+            .collect();
+
+        Some(GameState {
+            draw_pile: rest,
+            played_cards: snapshot.played_cards.clone(),
+            discard_pile: snapshot.discard_pile.clone(),
+            players,
+            remaining_bomb_count: snapshot.remaining_bomb_count,
+            remaining_hint_count: snapshot.remaining_hint_count,
+            has_half_hint: snapshot.has_half_hint,
+            turn: snapshot.num_rounds,
+            last_turn: snapshot.last_turn,
+            outcome: snapshot.outcome,
+            config: snapshot.game_config.clone(),
+            status: snapshot.status,
+        })
+    }
+}
+
+impl GameStateSnapshot {
+    /// Cards this player can't see: their own hand and the draw pile
+    pub fn unseen_cards(&self) -> Vec<Card> {
+        let mut unseen = self.game_config.variant.deck();
+        for card in self.visible_cards() {
+            if let Some(index) = unseen.iter().position(|c| *c == card) {
+                unseen.swap_remove(index);
+            }
+        }
+        unseen
+    }
+
+    /// A game this player can't tell apart from the real one. Their hand is dealt
+    /// from the cards they can't see following the hints each slot received, the
+    /// rest is shuffled into the draw pile. Every deal matching the hints is as
+    /// likely as it is in the real game, so the samples can be counted to tell the
+    /// odds of a card.
+    ///
+    /// Gives nothing when no deal matches, or when the played cards are hidden
+    /// (see `cards_in_hole`).
+    pub fn sample_game_state<R: Rng>(&self, rng: &mut R) -> Option<GameState> {
+        Deals::new(self)?.sample(rng)
+    }
+
+    /// Endless samples of `sample_game_state`, much quicker than asking for them
+    /// one by one. Empty when there's none to give.
+    pub fn sample_game_states<'a, R: Rng + 'a>(
+        &'a self,
+        mut rng: R,
+    ) -> impl Iterator<Item = GameState> + 'a {
+        let mut deals = Deals::new(self);
+        iter::from_fn(move || deals.as_mut()?.sample(&mut rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::model::{CardFace, CardSuit};
+    use crate::test_data::hinted_ones;
+
+    fn all_cards(game_state: &GameState) -> HashMap<Card, usize> {
+        game_state
+            .players
+            .iter()
+            .flat_map(|player| player.hand.iter().flatten().map(|slot| slot.card))
+            .chain(game_state.draw_pile.iter().copied())
+            .chain(game_state.discard_pile.iter().copied())
+            .chain(game_state.played_cards.cards())
+            .counts()
+    }
+
+    #[test]
+    fn test_samples_follow_the_hints() {
+        let (game_state, snapshot) = hinted_ones();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for sample in snapshot.sample_game_states(&mut rng).take(20) {
+            assert_eq!(sample.players[0], game_state.players[0]);
+            assert_eq!(all_cards(&sample), all_cards(&game_state));
+            assert_eq!(sample.draw_pile.len(), game_state.draw_pile.len());
+            for (sampled, real) in sample.players[1]
+                .hand
+                .iter()
+                .zip(&game_state.players[1].hand)
+            {
+                let (sampled, real) = (sampled.as_ref().unwrap(), real.as_ref().unwrap());
+                assert!(real.hints.is_possible(&sampled.card));
+                assert_eq!(sampled.hints, real.hints);
+                assert_eq!(sampled.draw_number, real.draw_number);
+            }
+        }
+    }
+
+    #[test]
+    fn test_deals_are_as_likely_as_in_the_real_game() {
+        let (game_state, snapshot) = hinted_ones();
+        let one = game_state.players[1]
+            .hand
+            .iter()
+            .position(|slot| slot.as_ref().unwrap().card.face == CardFace::One)
+            .unwrap();
+
+        // every one left is as likely, three copies of each are in the game
+        let unseen_ones = snapshot
+            .unseen_cards()
+            .into_iter()
+            .filter(|card| card.face == CardFace::One)
+            .collect_vec();
+        let red_share = unseen_ones
+            .iter()
+            .filter(|card| card.suit == CardSuit::Red)
+            .count() as f64
+            / unseen_ones.len() as f64;
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let samples = 2000;
+        let reds = snapshot
+            .sample_game_states(&mut rng)
+            .take(samples)
+            .filter(|sample| {
+                sample.players[1].hand[one].as_ref().unwrap().card
+                    == Card {
+                        face: CardFace::One,
+                        suit: CardSuit::Red,
+                    }
+            })
+            .count();
+        let sampled_share = reds as f64 / samples as f64;
+        assert!(
+            (sampled_share - red_share).abs() < 0.05,
+            "{sampled_share} instead of {red_share}"
+        );
+    }
+}
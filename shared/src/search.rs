@@ -0,0 +1,285 @@
+use std::time::{Duration, Instant};
+
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::bot::Bot;
+use crate::model::{
+    Card, CardClass, GameSnapshotEvent, GameState, GameStateSnapshot, HintAction, PlayerAction,
+    PlayerIndex, SlotIndex,
+};
+
+/// How long a `SearchBot` thinks about a move. It stops at whichever limit comes
+/// first, finishing the deal it's simulating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchBudget {
+    /// Deals of the hidden cards to simulate every candidate action on
+    pub samples: usize,
+    /// Not available on the web, where there's no clock to read
+    pub time_limit: Option<Duration>,
+}
+
+/// Samples only, a time limit has to be asked for since it doesn't work on the web
+impl Default for SearchBudget {
+    fn default() -> Self {
+        SearchBudget::samples(50)
+    }
+}
+
+impl SearchBudget {
+    pub fn samples(samples: usize) -> Self {
+        SearchBudget {
+            samples,
+            time_limit: None,
+        }
+    }
+
+    pub fn with_time_limit(self, time_limit: Duration) -> Self {
+        SearchBudget {
+            time_limit: Some(time_limit),
+            ..self
+        }
+    }
+}
+
+/// Plays the action scoring best on average over deals of the cards it can't see:
+/// its own hand, told apart only by the hints it received, and the draw pile.
+/// Each action is followed through to the end of the game with a quick policy.
+///
+/// Choosing an action keeps the thread busy for the whole budget. The lobby doesn't
+/// seat bots, a server that does has to ask them from a blocking task
+/// (`tokio::task::spawn_blocking`) rather than from the lobby's own.
+pub struct SearchBot {
+    rng: StdRng,
+    budget: SearchBudget,
+}
+
+impl SearchBot {
+    pub fn new(seed: u64, budget: SearchBudget) -> Self {
+        SearchBot {
+            rng: StdRng::seed_from_u64(seed),
+            budget,
+        }
+    }
+
+    /// How good each candidate action looks on average, in the candidates' order
+    pub fn rate_actions(
+        &mut self,
+        snapshot: &GameStateSnapshot,
+        candidates: &[PlayerAction],
+    ) -> Vec<f64> {
+        let started = self.budget.time_limit.map(|limit| (Instant::now(), limit));
+        let mut totals = vec![0.0; candidates.len()];
+        let mut samples = 0;
+        let mut deals = snapshot.sample_game_states(StdRng::seed_from_u64(self.rng.gen()));
+
+        while samples < self.budget.samples
+            && started.is_none_or(|(started, limit)| started.elapsed() < limit)
+        {
+            let Some(game_state) = deals.next() else {
+                break;
+            };
+            for (total, action) in totals.iter_mut().zip(candidates) {
+                *total += simulate(&game_state, *action, &mut self.rng);
+            }
+            samples += 1;
+        }
+
+        totals
+            .into_iter()
+            .map(|total| total / samples.max(1) as f64)
+            .collect()
+    }
+}
+
+impl Bot for SearchBot {
+    fn choose_action(
+        &mut self,
+        snapshot: &GameStateSnapshot,
+        _log: &[GameSnapshotEvent],
+    ) -> PlayerAction {
+        let candidates = snapshot
+            .legal_actions()
+            .into_iter()
+            .filter(|action| !matches!(action, PlayerAction::MoveSlot(_, _, _)))
+            .collect_vec();
+        if let [action] = candidates.as_slice() {
+            return *action;
+        }
+
+        let ratings = self.rate_actions(snapshot, &candidates);
+        candidates
+            .into_iter()
+            .zip(ratings)
+            // the first of equally good actions, plays come before discards and hints
+            .fold(
+                None,
+                |best: Option<(PlayerAction, f64)>, (action, rating)| match best {
+                    Some((_, best_rating)) if best_rating >= rating => best,
+                    _ => Some((action, rating)),
+                },
+            )
+            .map(|(action, _)| action)
+            .expect("a player always holds a card on their turn")
+    }
+}
+
+/// Rounds of the rollout policy played after a candidate action, long enough for the
+/// teammates to act on a hint
+const ROLLOUT_ROUNDS: usize = 2;
+
+/// How promising a game looks: the score so far and the score still within reach,
+/// with a little extra for the fuses, hints and discards left to spend
+fn evaluate(game_state: &GameState) -> f64 {
+    let score = game_state.played_cards.score() as f64;
+    if game_state.status.is_finished() {
+        return 2.0 * score;
+    }
+
+    score
+        + game_state.max_achievable_score() as f64
+        + 1.5 * game_state.remaining_bomb_count as f64
+        + 0.1 * game_state.remaining_hint_count as f64
+        + 0.3 * game_state.pace() as f64
+}
+
+/// How the game looks after playing an action then a few rounds of the rollout policy
+fn simulate<R: Rng>(game_state: &GameState, action: PlayerAction, rng: &mut R) -> f64 {
+    let mut game_state = game_state.clone();
+    let mut action = action;
+
+    for _ in 0..=ROLLOUT_ROUNDS * game_state.players.len() {
+        if game_state.status.is_finished() {
+            break;
+        }
+        let Ok(effects) = game_state.play(action) else {
+            break;
+        };
+        if game_state.run_effects(effects).is_err() {
+            break;
+        }
+        action = rollout_action(&game_state, rng);
+    }
+    evaluate(&game_state)
+}
+
+/// A quick policy for every player of a simulated game: play a card that's likely
+/// playable, otherwise hint a playable card, otherwise discard the oldest card
+/// without hints
+fn rollout_action<R: Rng>(game_state: &GameState, rng: &mut R) -> PlayerAction {
+    let player = game_state.current_player_index();
+    let hand = &game_state.players[player.0].hand;
+    let knowledge = game_state.hand_knowledge(player);
+
+    let playable_share = |slot: usize| {
+        let Some(Some(knowledge)) = knowledge.get(slot) else {
+            return 0.0;
+        };
+        let (playable, total) = knowledge.possible_cards().fold((0, 0), |(p, t), card| {
+            let is_playable = game_state.classify_card(&card) == CardClass::Playable;
+            (p + is_playable as usize, t + 1)
+        });
+        playable as f64 / total.max(1) as f64
+    };
+    let play = (0..hand.len())
+        .filter(|&slot| {
+            let touched = hand[slot].as_ref().is_some_and(|s| s.hints.is_touched());
+            let share = playable_share(slot);
+            share == 1.0 || (touched && share >= 0.5)
+        })
+        .map(|slot| PlayerAction::PlayCard(SlotIndex(slot)));
+
+    let num_players = game_state.players.len();
+    let variant = game_state.config.variant;
+    let hint = (1..num_players)
+        .filter(|_| game_state.remaining_hint_count > 0)
+        .map(|offset| PlayerIndex((player.0 + offset) % num_players))
+        .flat_map(|teammate| {
+            game_state.players[teammate.0]
+                .hand
+                .iter()
+                .flatten()
+                .filter(|slot| !slot.hints.is_touched())
+                .filter(|slot| game_state.classify_card(&slot.card) == CardClass::Playable)
+                .flat_map(|slot| hints_for(&slot.card))
+                .filter(|hint| variant.is_hint_allowed(hint))
+                .map(move |hint| PlayerAction::GiveHint(teammate, hint))
+                .collect_vec()
+        });
+
+    let discard = hand
+        .iter()
+        .enumerate()
+        .filter_map(|(index, slot)| Some((index, slot.as_ref()?)))
+        .min_by_key(|(_, slot)| (slot.hints.is_touched(), slot.draw_number))
+        .map(|(index, _)| PlayerAction::DiscardCard(SlotIndex(index)));
+
+    play.chain(hint)
+        .chain(discard)
+        .find(|action| game_state.play(*action).is_ok())
+        .or_else(|| {
+            game_state
+                .legal_actions(player)
+                .into_iter()
+                .filter(|action| !matches!(action, PlayerAction::MoveSlot(_, _, _)))
+                .collect_vec()
+                .choose(rng)
+                .copied()
+        })
+        .unwrap_or(PlayerAction::PlayCard(SlotIndex(0)))
+}
+
+fn hints_for(card: &Card) -> [HintAction; 2] {
+    [
+        HintAction::SameFace(card.face),
+        HintAction::SameSuit(card.suit),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_logic::GameLog;
+    use crate::model::{CardFace, GameConfig};
+    use crate::test_data::hinted_ones;
+
+    #[test]
+    fn test_plays_a_known_one() {
+        let (game_state, mut snapshot) = hinted_ones();
+        // hinting the ones back is fine too, but not without hints
+        snapshot.remaining_hint_count = 0;
+        let ones = game_state.players[1]
+            .hand
+            .iter()
+            .positions(|slot| slot.as_ref().unwrap().card.face == CardFace::One)
+            .map(|index| PlayerAction::PlayCard(SlotIndex(index)))
+            .collect_vec();
+        assert!(!ones.is_empty());
+
+        let action = SearchBot::new(0, SearchBudget::samples(10)).choose_action(&snapshot, &[]);
+        assert!(
+            ones.contains(&action),
+            "{action:?} instead of one of {ones:?}"
+        );
+    }
+
+    #[test]
+    fn test_default_budget_reads_no_clock() {
+        assert_eq!(SearchBudget::default().time_limit, None);
+    }
+
+    #[test]
+    fn test_stops_at_the_time_limit() {
+        let game_log = GameLog::new::<StdRng>(GameConfig::new(3, 1)).unwrap();
+        let names = vec!["p1".to_string(), "p2".to_string(), "p3".to_string()];
+        let snapshot =
+            game_log.into_client_game_state(game_log.current_game_state(), PlayerIndex(0), names);
+
+        let budget = SearchBudget::samples(usize::MAX).with_time_limit(Duration::from_millis(50));
+        let started = Instant::now();
+        SearchBot::new(0, budget).choose_action(&snapshot, &[]);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
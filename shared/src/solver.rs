@@ -0,0 +1,381 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use enum_map::EnumMap;
+use itertools::Itertools;
+use rand::{Rng, SeedableRng};
+
+use crate::model::{
+    Card, CardClass, CardFace, CardSuit, GameConfig, GameEffect, GameError, GameState, GameStatus,
+    HintAction, PlayerAction, PlayerIndex, SlotIndex,
+};
+
+/// What the solver found for a game played with every card visible
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    /// The best score of the lines the solver found
+    pub best_score: usize,
+    /// No line scores more than this, proven unless `is_exhaustive` says otherwise
+    pub upper_bound: usize,
+    /// The actions of the best line, from the state the solver started from
+    pub actions: Vec<PlayerAction>,
+    /// Positions the search went through
+    pub nodes: usize,
+}
+
+impl Solution {
+    /// Whether the best score is the best there is, the search didn't run out of budget
+    pub fn is_exhaustive(&self) -> bool {
+        self.best_score == self.upper_bound
+    }
+}
+
+/// Decides how well a seed can go for a table that sees every card. See `solve`.
+pub fn solve_seed<R: SeedableRng + Rng>(
+    config: &GameConfig,
+    node_budget: usize,
+) -> Result<Solution, GameError> {
+    let game_state = GameState::start_with_seed::<R>(config)?;
+    Ok(solve(&game_state, node_budget))
+}
+
+/// Searches for the best score the players can still reach from a game state, as if
+/// every player saw every card including the draw pile. Hints don't tell anything to
+/// such players, they only pass the turn. Misplays are only tried when discarding
+/// isn't allowed, otherwise a discard does the same without costing a fuse.
+///
+/// The line of always taking the first candidate action gives a score to beat.
+/// Higher scores are then tried from the highest one within reach down, the search
+/// stops at the first one a line reaches or when it went through `node_budget`
+/// positions.
+pub fn solve(game_state: &GameState, node_budget: usize) -> Solution {
+    let (best_score, best_line) = greedy_line(game_state);
+    let mut solver = Solver {
+        refuted: HashMap::new(),
+        nodes: 0,
+        node_budget,
+        best_score,
+        best_line,
+        line: vec![],
+    };
+
+    let mut upper_bound = upper_bound(game_state);
+    while upper_bound > solver.best_score {
+        match solver.reaches(game_state, upper_bound) {
+            Some(true) => break,
+            Some(false) => upper_bound -= 1,
+            None => break,
+        }
+    }
+
+    Solution {
+        best_score: solver.best_score,
+        upper_bound: upper_bound.max(solver.best_score),
+        actions: solver.best_line,
+        nodes: solver.nodes,
+    }
+}
+
+/// No line from this state scores more: every card left has to be within reach,
+/// and each turn plays at most one card.
+///
+/// Cards deep in the draw pile bound it further. Until the card of the `draws`th
+/// draw from now shows up, only the cards in hand and the ones drawn before it can
+/// be played, after it there's a turn for each card left to draw, then the last round.
+fn upper_bound(game_state: &GameState) -> usize {
+    let score = game_state.played_cards.score();
+    let max_score = game_state.max_achievable_score();
+    let last_round = match game_state.status {
+        GameStatus::Finished(_) => return score,
+        GameStatus::LastRound { turns_remaining } => {
+            return max_score.min(score + turns_remaining as usize)
+        }
+        _ => game_state.players.len(),
+    };
+
+    // how many draws from now each card shows up, cards in hand are there already
+    let mut first_draw: EnumMap<CardSuit, EnumMap<CardFace, usize>> =
+        EnumMap::from_fn(|_| EnumMap::from_fn(|_| usize::MAX));
+    for (index, card) in game_state.draw_pile.iter().enumerate() {
+        first_draw[card.suit][card.face] = game_state.draw_pile.len() - index;
+    }
+    for slot in game_state
+        .players
+        .iter()
+        .flat_map(|player| player.hand.iter().flatten())
+    {
+        first_draw[slot.card.suit][slot.card.face] = 0;
+    }
+
+    let variant = game_state.config.variant;
+    let draw_pile = game_state.draw_pile.len();
+    (0..draw_pile)
+        .map(|draws| {
+            let before = variant.max_score_with(&game_state.played_cards, |card| {
+                first_draw[card.suit][card.face] <= draws
+            });
+            before + draw_pile - draws - 1 + last_round
+        })
+        .fold(max_score.min(score + draw_pile + last_round), usize::min)
+}
+
+/// What tells positions apart: the order of the cards in a hand doesn't matter
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Position {
+    played_cards: Vec<Card>,
+    hands: Vec<Vec<Card>>,
+    draw_pile: usize,
+    hints: u8,
+    has_half_hint: bool,
+    fuses: u8,
+    player: PlayerIndex,
+    status: GameStatus,
+}
+
+impl Position {
+    fn new(game_state: &GameState) -> Self {
+        Position {
+            played_cards: game_state.played_cards.cards().collect(),
+            hands: game_state
+                .players
+                .iter()
+                .map(|player| {
+                    player
+                        .hand
+                        .iter()
+                        .flatten()
+                        .map(|slot| slot.card)
+                        .sorted_by_key(|card| (card.suit, card.face))
+                        .collect()
+                })
+                .collect(),
+            draw_pile: game_state.draw_pile.len(),
+            hints: game_state.remaining_hint_count,
+            has_half_hint: game_state.has_half_hint,
+            fuses: game_state.remaining_bomb_count,
+            player: game_state.current_player_index(),
+            status: game_state.status,
+        }
+    }
+}
+
+struct Solver {
+    /// The lowest score each position is known to fall short of
+    refuted: HashMap<Position, usize>,
+    nodes: usize,
+    node_budget: usize,
+    best_score: usize,
+    best_line: Vec<PlayerAction>,
+    line: Vec<PlayerAction>,
+}
+
+impl Solver {
+    /// Whether a line from the state reaches the target score, nothing once the
+    /// budget runs out
+    fn reaches(&mut self, game_state: &GameState, target: usize) -> Option<bool> {
+        let score = game_state.played_cards.score();
+        if score > self.best_score {
+            self.best_score = score;
+            self.best_line = self.line.clone();
+        }
+        if score >= target {
+            return Some(true);
+        }
+        if game_state.status.is_finished() || upper_bound(game_state) < target {
+            return Some(false);
+        }
+
+        let position = Position::new(game_state);
+        if self
+            .refuted
+            .get(&position)
+            .is_some_and(|&refuted| refuted <= target)
+        {
+            return Some(false);
+        }
+
+        self.nodes += 1;
+        if self.nodes > self.node_budget {
+            return None;
+        }
+
+        for action in candidate_actions(game_state) {
+            let Some(next) = apply(game_state, action) else {
+                continue;
+            };
+            self.line.push(action);
+            let reached = self.reaches(&next, target);
+            self.line.pop();
+            if reached != Some(false) {
+                return reached;
+            }
+        }
+
+        self.refuted.insert(position, target);
+        Some(false)
+    }
+}
+
+/// The score of always taking the first candidate action, and the actions
+fn greedy_line(game_state: &GameState) -> (usize, Vec<PlayerAction>) {
+    let mut game_state = game_state.clone();
+    let mut line = vec![];
+    while !game_state.status.is_finished() {
+        let Some((action, next)) = candidate_actions(&game_state)
+            .into_iter()
+            .find_map(|action| Some((action, apply(&game_state, action)?)))
+        else {
+            break;
+        };
+        line.push(action);
+        game_state = next;
+    }
+    (game_state.played_cards.score(), line)
+}
+
+/// The state after an action, leaving out what hints tell since everyone sees
+/// everything anyway
+fn apply(game_state: &GameState, action: PlayerAction) -> Option<GameState> {
+    let effects = game_state.play(action).ok()?;
+    let mut next = game_state.clone();
+    next.run_effects(
+        effects
+            .into_iter()
+            .filter(|effect| !matches!(effect, GameEffect::HintCard(_, _, _, _)))
+            .collect(),
+    )
+    .ok()?;
+    Some(next)
+}
+
+/// The actions worth trying, the most promising first: plays, then a hint to pass
+/// the turn, then discards from the least to the most useful card. Copies of the
+/// same card in a hand are only tried once, any hint will do, and with a useless
+/// card to throw away no other card is discarded.
+fn candidate_actions(game_state: &GameState) -> Vec<PlayerAction> {
+    let player = game_state.current_player_index();
+    let hand = game_state.players[player.0]
+        .hand
+        .iter()
+        .enumerate()
+        .filter_map(|(index, slot)| Some((SlotIndex(index), slot.as_ref()?.card)))
+        .unique_by(|(_, card)| *card)
+        .map(|(slot, card)| (slot, card, game_state.classify_card(&card)))
+        .collect_vec();
+
+    let plays = hand
+        .iter()
+        .filter(|(_, _, class)| *class == CardClass::Playable)
+        .sorted_by_key(|(_, card, _)| card.face)
+        .map(|(slot, _, _)| PlayerAction::PlayCard(*slot));
+
+    let num_players = game_state.players.len();
+    let hint = (1..num_players)
+        .filter(|_| game_state.remaining_hint_count > 0)
+        .map(|offset| PlayerIndex((player.0 + offset) % num_players))
+        .flat_map(|teammate| {
+            game_state.players[teammate.0]
+                .hand
+                .iter()
+                .flatten()
+                .flat_map(move |slot| {
+                    [
+                        HintAction::SameFace(slot.card.face),
+                        HintAction::SameSuit(slot.card.suit),
+                    ]
+                    .map(|hint| PlayerAction::GiveHint(teammate, hint))
+                })
+        })
+        .find(|action| game_state.play(*action).is_ok());
+
+    let has_trash = hand.iter().any(|(_, _, class)| *class == CardClass::Trash);
+    let can_discard = hand
+        .first()
+        .is_some_and(|(slot, _, _)| game_state.play(PlayerAction::DiscardCard(*slot)).is_ok());
+    let discards = hand
+        .iter()
+        .filter(|(_, _, class)| !has_trash || *class == CardClass::Trash)
+        .sorted_by_key(|(_, card, class)| {
+            let usefulness = match class {
+                CardClass::Trash => 0,
+                CardClass::Normal => 1,
+                CardClass::Playable => 2,
+                CardClass::Critical => 3,
+            };
+            // higher cards are needed later
+            (usefulness, Reverse(card.face))
+        })
+        .filter_map(|(slot, _, class)| {
+            if can_discard {
+                Some(PlayerAction::DiscardCard(*slot))
+            } else {
+                // playable cards are played already
+                (*class != CardClass::Playable).then_some(PlayerAction::PlayCard(*slot))
+            }
+        });
+
+    plays.chain(hint).chain(discards).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::model::RuleValidation;
+
+    /// Plays a line from the start, through the engine with every check it does
+    fn replay(game_state: &GameState, actions: &[PlayerAction]) -> GameState {
+        let mut game_state = game_state.clone();
+        for action in actions {
+            let player = game_state.current_player_index();
+            game_state.check_actor(player, action).unwrap();
+            let effects = game_state.play(*action).unwrap();
+            game_state.run_effects(effects).unwrap();
+        }
+        game_state
+    }
+
+    #[test]
+    fn test_finds_perfect_lines() {
+        for seed in 0..3 {
+            let config = GameConfig::new(3, seed).with_rules(RuleValidation::Strict);
+            let game_state = GameState::start_with_seed::<StdRng>(&config).unwrap();
+            let solution = solve(&game_state, 100_000);
+
+            assert_eq!(solution.best_score, 25, "seed {seed}");
+            assert!(solution.is_exhaustive());
+            let end = replay(&game_state, &solution.actions);
+            assert_eq!(end.played_cards.score(), 25);
+        }
+    }
+
+    #[test]
+    fn test_proves_lost_cards_cap_the_score() {
+        let config = GameConfig::new(2, 7);
+        let mut game_state = GameState::start_with_seed::<StdRng>(&config).unwrap();
+        // both copies of the red two are gone, red can't go past one
+        let red_two = Card {
+            face: CardFace::Two,
+            suit: CardSuit::Red,
+        };
+        for player in game_state.players.iter_mut() {
+            for slot in player.hand.iter_mut().flatten() {
+                if slot.card == red_two {
+                    slot.card = Card {
+                        face: CardFace::One,
+                        suit: CardSuit::Blue,
+                    };
+                }
+            }
+        }
+        game_state.draw_pile.retain(|card| *card != red_two);
+        game_state.discard_pile = vec![red_two, red_two];
+
+        let solution = solve(&game_state, 100_000);
+        assert!(solution.upper_bound <= 21);
+        assert_eq!(solution.best_score, solution.upper_bound);
+        let end = replay(&game_state, &solution.actions);
+        assert_eq!(end.played_cards.score(), solution.best_score);
+    }
+}
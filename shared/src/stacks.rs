@@ -0,0 +1,105 @@
+use crate::model::{Card, CardFace, CardSuit, PlayedStacks};
+
+impl PlayedStacks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, card: Card) {
+        self.stacks[card.suit].push(card.face);
+    }
+
+    /// Faces of a suit, in the order they were played
+    pub fn stack(&self, suit: CardSuit) -> &[CardFace] {
+        &self.stacks[suit]
+    }
+
+    /// The last card played on a suit
+    pub fn top_card(&self, suit: CardSuit) -> Option<Card> {
+        self.stacks[suit].last().map(|&face| Card { face, suit })
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.stacks[card.suit].contains(&card.face)
+    }
+
+    /// Every played card counts for one point, whatever the variant
+    pub fn score(&self) -> usize {
+        self.stacks.values().map(|stack| stack.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.score() == 0
+    }
+
+    /// Every played card, suit by suit
+    pub fn cards(&self) -> impl Iterator<Item = Card> + '_ {
+        self.stacks
+            .iter()
+            .flat_map(|(suit, stack)| stack.iter().map(move |&face| Card { face, suit }))
+    }
+}
+
+impl FromIterator<Card> for PlayedStacks {
+    fn from_iter<T: IntoIterator<Item = Card>>(cards: T) -> Self {
+        let mut played_stacks = PlayedStacks::new();
+        for card in cards {
+            played_stacks.push(card);
+        }
+        played_stacks
+    }
+}
+
+impl From<Vec<Card>> for PlayedStacks {
+    fn from(cards: Vec<Card>) -> Self {
+        cards.into_iter().collect()
+    }
+}
+
+impl From<PlayedStacks> for Vec<Card> {
+    fn from(played_stacks: PlayedStacks) -> Self {
+        played_stacks.cards().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CardFace::*;
+    use CardSuit::*;
+
+    fn card(face: CardFace, suit: CardSuit) -> Card {
+        Card { face, suit }
+    }
+
+    #[test]
+    fn test_stacks_by_suit() {
+        let played_cards = vec![
+            card(One, Red),
+            card(One, Blue),
+            card(Two, Red),
+            card(Three, Red),
+        ];
+        let played_stacks = PlayedStacks::from(played_cards.clone());
+
+        assert_eq!(played_stacks.stack(Red), &[One, Two, Three]);
+        assert_eq!(played_stacks.top_card(Red), Some(card(Three, Red)));
+        assert_eq!(played_stacks.top_card(Green), None);
+        assert!(played_stacks.contains(&card(One, Blue)));
+        assert!(!played_stacks.contains(&card(Two, Blue)));
+        assert_eq!(played_stacks.score(), 4);
+
+        // the flat list is grouped by suit, keeping the order within each suit
+        let flattened: Vec<Card> = played_stacks.clone().into();
+        assert_eq!(
+            flattened,
+            vec![
+                card(One, Red),
+                card(Two, Red),
+                card(Three, Red),
+                card(One, Blue),
+            ]
+        );
+        assert_eq!(PlayedStacks::from(flattened), played_stacks);
+    }
+}
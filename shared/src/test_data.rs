@@ -0,0 +1,21 @@
+use rand::rngs::StdRng;
+
+use crate::client_logic::GameLog;
+use crate::model::{
+    CardFace, GameConfig, GameState, GameStateSnapshot, HintAction, PlayerAction, PlayerIndex,
+};
+
+/// A 2 player game where the second player was just told about their ones
+pub(crate) fn hinted_ones() -> (GameState, GameStateSnapshot) {
+    let mut game_log = GameLog::new::<StdRng>(GameConfig::new(2, 3)).unwrap();
+    game_log
+        .log(
+            PlayerIndex(0),
+            PlayerAction::GiveHint(PlayerIndex(1), HintAction::SameFace(CardFace::One)),
+        )
+        .unwrap();
+    let game_state = game_log.current_game_state();
+    let names = vec!["p1".to_string(), "p2".to_string()];
+    let snapshot = game_log.into_client_game_state(game_state.clone(), PlayerIndex(1), names);
+    (game_state, snapshot)
+}
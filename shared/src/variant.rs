@@ -0,0 +1,736 @@
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+use crate::model::{
+    Card, CardClass, CardFace, CardSuit, HintAction, HintKnowledge, PlayedCardResult, PlayedStacks,
+    StackDirection, Variant,
+};
+
+/// Copies of each face in a regular suit
+static STANDARD_COPIES: [(CardFace, usize); 5] = [
+    (CardFace::One, 3),
+    (CardFace::Two, 2),
+    (CardFace::Three, 2),
+    (CardFace::Four, 2),
+    (CardFace::Five, 1),
+];
+
+/// Copies of each face in an Up or Down suit, both ends of the stack are unique
+static UP_OR_DOWN_COPIES: [(CardFace, usize); 6] = [
+    (CardFace::One, 1),
+    (CardFace::Two, 2),
+    (CardFace::Three, 2),
+    (CardFace::Four, 2),
+    (CardFace::Five, 1),
+    (CardFace::Start, 1),
+];
+
+/// Copies of each face in a dark suit
+static SINGLE_COPIES: [(CardFace, usize); 5] = [
+    (CardFace::One, 1),
+    (CardFace::Two, 1),
+    (CardFace::Three, 1),
+    (CardFace::Four, 1),
+    (CardFace::Five, 1),
+];
+
+impl CardFace {
+    /// Every face but the start card, the faces a face hint can name
+    pub fn numbered() -> impl Iterator<Item = CardFace> {
+        CardFace::iter().filter(|face| *face != CardFace::Start)
+    }
+}
+
+impl Variant {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Variant::Standard => "Standard",
+            Variant::Rainbow => "Rainbow (6 Suits)",
+            Variant::Black => "Black (6 Suits)",
+            Variant::UpOrDown => "Up or Down",
+            Variant::ThrowItInAHole => "Throw It In A Hole",
+        }
+    }
+
+    /// Suits in play, in the same order the deck is built.
+    pub fn suits(&self) -> Vec<CardSuit> {
+        let standard_suits = vec![
+            CardSuit::Red,
+            CardSuit::Green,
+            CardSuit::Yellow,
+            CardSuit::White,
+            CardSuit::Blue,
+        ];
+
+        match self {
+            Variant::Standard | Variant::UpOrDown | Variant::ThrowItInAHole => standard_suits,
+            Variant::Rainbow => [standard_suits, vec![CardSuit::Rainbow]].concat(),
+            Variant::Black => [standard_suits, vec![CardSuit::Black]].concat(),
+        }
+    }
+
+    /// Faces in the deck, including the start card when the variant has one
+    pub fn faces(&self) -> Vec<CardFace> {
+        match self {
+            Variant::UpOrDown => CardFace::iter().collect(),
+            _ => self.ranks(),
+        }
+    }
+
+    /// The numbered faces, a full stack holds one card of each
+    pub fn ranks(&self) -> Vec<CardFace> {
+        CardFace::numbered().collect()
+    }
+
+    /// Deck composition of a suit, as copies per face
+    pub fn suit_copies(&self, suit: CardSuit) -> &'static [(CardFace, usize)] {
+        match (self, suit) {
+            (_, CardSuit::Black) => &SINGLE_COPIES,
+            (Variant::UpOrDown, _) => &UP_OR_DOWN_COPIES,
+            _ => &STANDARD_COPIES,
+        }
+    }
+
+    /// Number of copies of a card in the deck (0 when the card isn't part of this variant)
+    pub fn copies(&self, card: &Card) -> usize {
+        if !self.suits().contains(&card.suit) {
+            return 0;
+        }
+
+        self.suit_copies(card.suit)
+            .iter()
+            .find(|(face, _)| *face == card.face)
+            .map_or(0, |(_, copies)| *copies)
+    }
+
+    /// Every card in the deck, unshuffled
+    pub fn deck(&self) -> Vec<Card> {
+        let suits = self.suits();
+        self.faces()
+            .into_iter()
+            .flat_map(|face| {
+                suits.iter().flat_map(move |&suit| {
+                    let card = Card { face, suit };
+                    vec![card; self.copies(&card)]
+                })
+            })
+            .collect()
+    }
+
+    pub fn num_cards(&self) -> usize {
+        self.deck().len()
+    }
+
+    pub fn max_score(&self) -> usize {
+        self.suits().len() * self.ranks().len()
+    }
+
+    /// Played cards go face down, players only learn the strike count and not which
+    /// card went in the hole until the game is over. Completing a stack gives no
+    /// clue back.
+    pub fn hides_play_results(&self) -> bool {
+        *self == Variant::ThrowItInAHole
+    }
+
+    /// Can this hint be given at all in this variant?
+    pub fn is_hint_allowed(&self, hint: &HintAction) -> bool {
+        match hint {
+            HintAction::SameSuit(CardSuit::Rainbow) => false,
+            HintAction::SameSuit(suit) => self.suits().contains(suit),
+            HintAction::SameFace(face) => self.ranks().contains(face),
+        }
+    }
+
+    /// Does the hint touch the card?
+    pub fn hint_touches(&self, card: &Card, hint: &HintAction) -> bool {
+        hint.touches(card)
+    }
+
+    /// Suits a card could still be, given the hints it received.
+    /// A card touched by two different colors can only be a rainbow card.
+    pub fn possible_suits(&self, hints: &HintKnowledge) -> Vec<CardSuit> {
+        self.suits()
+            .into_iter()
+            .filter(|&suit| {
+                self.faces()
+                    .into_iter()
+                    .any(|face| hints.is_possible(&Card { face, suit }))
+            })
+            .collect()
+    }
+
+    /// Which way the stack of a suit goes, stacks are always built upward outside of Up or Down
+    pub fn stack_direction(&self, played_cards: &PlayedStacks, suit: CardSuit) -> StackDirection {
+        if *self != Variant::UpOrDown {
+            return StackDirection::Up;
+        }
+
+        let mut stack = played_cards.stack(suit).iter().copied();
+
+        match (stack.next(), stack.next()) {
+            (Some(CardFace::One), _) | (Some(CardFace::Start), Some(CardFace::Two)) => {
+                StackDirection::Up
+            }
+            (Some(CardFace::Five), _) | (Some(CardFace::Start), Some(CardFace::Four)) => {
+                StackDirection::Down
+            }
+            _ => StackDirection::Undecided,
+        }
+    }
+
+    pub fn check_play(&self, played_cards: &PlayedStacks, card_played: &Card) -> PlayedCardResult {
+        if *self == Variant::UpOrDown {
+            return self.check_up_or_down_play(played_cards, card_played);
+        }
+
+        // Is the previous required card on top of its stack? Good!
+        // Note: If prev_card is None, then the stack has to be empty.
+        if played_cards.top_card(card_played.suit) != card_played.prev_card() {
+            return PlayedCardResult::Rejected;
+        }
+
+        if card_played.is_final_set_card() {
+            PlayedCardResult::CompletedSet
+        } else {
+            PlayedCardResult::Accepted
+        }
+    }
+
+    fn check_up_or_down_play(
+        &self,
+        played_cards: &PlayedStacks,
+        card_played: &Card,
+    ) -> PlayedCardResult {
+        use CardFace::*;
+
+        let top_face = played_cards.top_card(card_played.suit).map(|c| c.face);
+        let next_faces = match (
+            top_face,
+            self.stack_direction(played_cards, card_played.suit),
+        ) {
+            (None, _) => vec![One, Five, Start],
+            (Some(Start), _) => vec![Two, Four],
+            (Some(_), _) if self.is_suit_complete(played_cards, card_played.suit) => vec![],
+            (Some(face), StackDirection::Up) => Card {
+                face,
+                suit: card_played.suit,
+            }
+            .next_face()
+            .into_iter()
+            .collect(),
+            (Some(face), _) => Card {
+                face,
+                suit: card_played.suit,
+            }
+            .prev_face()
+            .into_iter()
+            .collect(),
+        };
+
+        if !next_faces.contains(&card_played.face) {
+            return PlayedCardResult::Rejected;
+        }
+
+        if played_cards.stack(card_played.suit).len() + 1 == self.ranks().len() {
+            PlayedCardResult::CompletedSet
+        } else {
+            PlayedCardResult::Accepted
+        }
+    }
+
+    /// A suit is complete once its stack holds as many cards as there are ranks,
+    /// a start card stands in for the 1 or the 5 it replaced
+    pub fn is_suit_complete(&self, played_cards: &PlayedStacks, suit: CardSuit) -> bool {
+        played_cards.stack(suit).len() >= self.ranks().len()
+    }
+
+    pub fn is_all_sets_complete(&self, played_cards: &PlayedStacks) -> bool {
+        self.suits()
+            .into_iter()
+            .all(|suit| self.is_suit_complete(played_cards, suit))
+    }
+
+    /// A card is lost once all of its copies are in the discard pile before it was played
+    pub fn is_card_lost(
+        &self,
+        played_cards: &PlayedStacks,
+        discard_pile: &[Card],
+        card: &Card,
+    ) -> bool {
+        !played_cards.contains(card)
+            && discard_pile.iter().filter(|c| *c == card).count() >= self.copies(card)
+    }
+
+    /// The best score still reachable, a stack can't grow past a lost card
+    pub fn max_achievable_score(
+        &self,
+        played_cards: &PlayedStacks,
+        discard_pile: &[Card],
+    ) -> usize {
+        self.max_score_with(played_cards, |card| {
+            !self.is_card_lost(played_cards, discard_pile, card)
+        })
+    }
+
+    /// The best score reachable playing only the cards that are available, a stack
+    /// can't grow past a card that isn't
+    pub fn max_score_with(
+        &self,
+        played_cards: &PlayedStacks,
+        is_available: impl Fn(&Card) -> bool,
+    ) -> usize {
+        self.suits()
+            .into_iter()
+            .map(|suit| self.max_suit_score(played_cards, suit, &is_available))
+            .sum()
+    }
+
+    fn max_suit_score(
+        &self,
+        played_cards: &PlayedStacks,
+        suit: CardSuit,
+        is_available: &impl Fn(&Card) -> bool,
+    ) -> usize {
+        self.open_directions(played_cards, suit)
+            .into_iter()
+            .map(|direction| {
+                self.reachable_steps(
+                    played_cards,
+                    suit,
+                    &self.build_order(direction),
+                    is_available,
+                )
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Faces in the order a stack is built, each step lists the faces that can fill it
+    fn build_order(&self, direction: StackDirection) -> Vec<Vec<CardFace>> {
+        use CardFace::*;
+        match (self, direction) {
+            (Variant::UpOrDown, StackDirection::Down) => {
+                vec![
+                    vec![Five, Start],
+                    vec![Four],
+                    vec![Three],
+                    vec![Two],
+                    vec![One],
+                ]
+            }
+            (Variant::UpOrDown, _) => {
+                vec![
+                    vec![One, Start],
+                    vec![Two],
+                    vec![Three],
+                    vec![Four],
+                    vec![Five],
+                ]
+            }
+            _ => self.ranks().into_iter().map(|face| vec![face]).collect(),
+        }
+    }
+
+    /// Directions the stack of a suit can still be built in
+    fn open_directions(&self, played_cards: &PlayedStacks, suit: CardSuit) -> Vec<StackDirection> {
+        match self.stack_direction(played_cards, suit) {
+            StackDirection::Undecided => vec![StackDirection::Up, StackDirection::Down],
+            direction => vec![direction],
+        }
+    }
+
+    /// Steps of the build order that are filled or can still be, the first step no
+    /// available card fills ends the stack
+    fn reachable_steps(
+        &self,
+        played_cards: &PlayedStacks,
+        suit: CardSuit,
+        build_order: &[Vec<CardFace>],
+        is_available: &impl Fn(&Card) -> bool,
+    ) -> usize {
+        let stack_len = played_cards.stack(suit).len();
+        build_order
+            .iter()
+            .enumerate()
+            .take_while(|(step, faces)| {
+                *step < stack_len || faces.iter().any(|&face| is_available(&Card { face, suit }))
+            })
+            .count()
+    }
+
+    /// Already played, or out of reach because a card it builds on is lost
+    pub fn is_trash(
+        &self,
+        played_cards: &PlayedStacks,
+        discard_pile: &[Card],
+        card: &Card,
+    ) -> bool {
+        let stack_len = played_cards.stack(card.suit).len();
+        played_cards.contains(card)
+            || self
+                .open_directions(played_cards, card.suit)
+                .into_iter()
+                .all(|direction| {
+                    let build_order = self.build_order(direction);
+                    match build_order
+                        .iter()
+                        .position(|faces| faces.contains(&card.face))
+                    {
+                        Some(step) => {
+                            step < stack_len
+                                || step
+                                    >= self.reachable_steps(
+                                        played_cards,
+                                        card.suit,
+                                        &build_order,
+                                        &|card| {
+                                            !self.is_card_lost(played_cards, discard_pile, card)
+                                        },
+                                    )
+                        }
+                        None => true,
+                    }
+                })
+    }
+
+    /// Losing this copy lowers the best score its suit can still reach
+    fn is_critical(&self, played_cards: &PlayedStacks, discard_pile: &[Card], card: &Card) -> bool {
+        let max_suit_score = |discard_pile: &[Card]| {
+            self.max_suit_score(played_cards, card.suit, &|card| {
+                !self.is_card_lost(played_cards, discard_pile, card)
+            })
+        };
+        let without_copy = discard_pile.iter().copied().chain([*card]).collect_vec();
+        max_suit_score(&without_copy) < max_suit_score(discard_pile)
+    }
+
+    pub fn classify_card(
+        &self,
+        played_cards: &PlayedStacks,
+        discard_pile: &[Card],
+        card: &Card,
+    ) -> CardClass {
+        if self.check_play(played_cards, card) != PlayedCardResult::Rejected {
+            CardClass::Playable
+        } else if self.is_trash(played_cards, discard_pile, card) {
+            CardClass::Trash
+        } else if self.is_critical(played_cards, discard_pile, card) {
+            CardClass::Critical
+        } else {
+            CardClass::Normal
+        }
+    }
+
+    pub fn all_variants() -> Vec<Variant> {
+        Variant::iter().collect_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Hint;
+
+    #[test]
+    fn test_standard_deck_composition() {
+        let deck = Variant::Standard.deck();
+
+        assert_eq!(deck.len(), 50);
+        assert_eq!(Variant::Standard.num_cards(), 50);
+        assert_eq!(Variant::Standard.max_score(), 25);
+        assert_eq!(
+            deck.iter()
+                .filter(|c| c.face == CardFace::One && c.suit == CardSuit::Red)
+                .count(),
+            3
+        );
+        assert_eq!(
+            deck.iter()
+                .filter(|c| c.face == CardFace::Five && c.suit == CardSuit::Blue)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_rainbow_deck_composition() {
+        let deck = Variant::Rainbow.deck();
+
+        assert_eq!(deck.len(), 60);
+        assert_eq!(Variant::Rainbow.max_score(), 30);
+        assert_eq!(
+            deck.iter().filter(|c| c.suit == CardSuit::Rainbow).count(),
+            10
+        );
+        assert_eq!(
+            Variant::Standard.copies(&Card {
+                face: CardFace::One,
+                suit: CardSuit::Rainbow
+            }),
+            0
+        );
+    }
+
+    #[test]
+    fn test_black_deck_composition() {
+        let deck = Variant::Black.deck();
+
+        assert_eq!(deck.len(), 55);
+        assert_eq!(Variant::Black.num_cards(), 55);
+        assert_eq!(Variant::Black.max_score(), 30);
+        for face in Variant::Black.faces() {
+            let black_card = Card {
+                face,
+                suit: CardSuit::Black,
+            };
+            assert_eq!(deck.iter().filter(|c| **c == black_card).count(), 1);
+        }
+        assert!(Variant::Black.is_hint_allowed(&HintAction::SameSuit(CardSuit::Black)));
+        assert!(!Variant::Black.hint_touches(
+            &Card {
+                face: CardFace::One,
+                suit: CardSuit::Black
+            },
+            &HintAction::SameSuit(CardSuit::Red)
+        ));
+    }
+
+    #[test]
+    fn test_up_or_down_deck_composition() {
+        let deck = Variant::UpOrDown.deck();
+
+        assert_eq!(deck.len(), 45);
+        assert_eq!(Variant::UpOrDown.max_score(), 25);
+        assert_eq!(deck.iter().filter(|c| c.face == CardFace::Start).count(), 5);
+        assert!(!Variant::UpOrDown.is_hint_allowed(&HintAction::SameFace(CardFace::Start)));
+        assert!(!Variant::Standard.faces().contains(&CardFace::Start));
+    }
+
+    #[test]
+    fn test_up_or_down_stacks() {
+        let red = |face| Card {
+            face,
+            suit: CardSuit::Red,
+        };
+        let variant = Variant::UpOrDown;
+
+        assert_eq!(
+            variant.stack_direction(&PlayedStacks::new(), CardSuit::Red),
+            StackDirection::Undecided
+        );
+        assert_eq!(
+            variant.check_play(&PlayedStacks::new(), &red(CardFace::Five)),
+            PlayedCardResult::Accepted
+        );
+        assert_eq!(
+            variant.check_play(&PlayedStacks::new(), &red(CardFace::Two)),
+            PlayedCardResult::Rejected
+        );
+
+        let going_down = PlayedStacks::from(vec![red(CardFace::Five), red(CardFace::Four)]);
+        assert_eq!(
+            variant.stack_direction(&going_down, CardSuit::Red),
+            StackDirection::Down
+        );
+        assert_eq!(
+            variant.check_play(&going_down, &red(CardFace::Three)),
+            PlayedCardResult::Accepted
+        );
+        assert_eq!(
+            variant.check_play(&going_down, &red(CardFace::Five)),
+            PlayedCardResult::Rejected
+        );
+
+        let started = PlayedStacks::from(vec![red(CardFace::Start)]);
+        assert_eq!(
+            variant.stack_direction(&started, CardSuit::Red),
+            StackDirection::Undecided
+        );
+        assert_eq!(
+            variant.check_play(&started, &red(CardFace::Four)),
+            PlayedCardResult::Accepted
+        );
+        assert_eq!(
+            variant.check_play(&started, &red(CardFace::One)),
+            PlayedCardResult::Rejected
+        );
+
+        let mut almost_up = PlayedStacks::from(vec![
+            red(CardFace::Start),
+            red(CardFace::Two),
+            red(CardFace::Three),
+            red(CardFace::Four),
+        ]);
+        assert_eq!(
+            variant.stack_direction(&almost_up, CardSuit::Red),
+            StackDirection::Up
+        );
+        assert_eq!(
+            variant.check_play(&almost_up, &red(CardFace::Five)),
+            PlayedCardResult::CompletedSet
+        );
+        almost_up.push(red(CardFace::Five));
+        assert!(variant.is_suit_complete(&almost_up, CardSuit::Red));
+        assert_eq!(
+            Variant::Standard.stack_direction(&PlayedStacks::new(), CardSuit::Red),
+            StackDirection::Up
+        );
+    }
+
+    #[test]
+    fn test_max_achievable_score() {
+        let red = |face| Card {
+            face,
+            suit: CardSuit::Red,
+        };
+        let played_cards = PlayedStacks::from(vec![red(CardFace::One)]);
+        let variant = Variant::Standard;
+
+        assert_eq!(variant.max_achievable_score(&played_cards, &[]), 25);
+        // a single lost copy still leaves the other one
+        assert_eq!(
+            variant.max_achievable_score(&played_cards, &[red(CardFace::Three)]),
+            25
+        );
+        assert_eq!(
+            variant
+                .max_achievable_score(&played_cards, &[red(CardFace::Three), red(CardFace::Three)]),
+            22
+        );
+        // extra copies of a played card don't matter
+        assert_eq!(
+            variant.max_achievable_score(&played_cards, &[red(CardFace::One); 2]),
+            25
+        );
+
+        // up or down can still build the other way around a lost card
+        let variant = Variant::UpOrDown;
+        assert_eq!(
+            variant.max_achievable_score(&PlayedStacks::new(), &[red(CardFace::One)]),
+            25
+        );
+        assert_eq!(
+            variant.max_achievable_score(
+                &PlayedStacks::new(),
+                &[red(CardFace::Four), red(CardFace::Four)]
+            ),
+            23
+        );
+        let going_up = PlayedStacks::from(vec![red(CardFace::One)]);
+        assert_eq!(
+            variant.max_achievable_score(&going_up, &[red(CardFace::Five)]),
+            24
+        );
+    }
+
+    #[test]
+    fn test_classify_card() {
+        use CardFace::*;
+        let red = |face| Card {
+            face,
+            suit: CardSuit::Red,
+        };
+        let played_cards = PlayedStacks::from(vec![red(One), red(Two)]);
+        let discard_pile = [red(Four), red(Four), red(Three)];
+        let variant = Variant::Standard;
+        let classify = |card| variant.classify_card(&played_cards, &discard_pile, &card);
+
+        assert_eq!(classify(red(Three)), CardClass::Playable);
+        assert_eq!(classify(red(One)), CardClass::Trash);
+        assert_eq!(classify(red(Five)), CardClass::Trash);
+        assert_eq!(
+            classify(Card {
+                face: Five,
+                suit: CardSuit::Blue
+            }),
+            CardClass::Critical
+        );
+        assert_eq!(
+            classify(Card {
+                face: Two,
+                suit: CardSuit::Blue
+            }),
+            CardClass::Normal
+        );
+
+        // the start card can only begin a stack
+        let variant = Variant::UpOrDown;
+        let going_up = PlayedStacks::from(vec![red(One)]);
+        assert_eq!(
+            variant.classify_card(&going_up, &[], &red(Start)),
+            CardClass::Trash
+        );
+        assert_eq!(
+            variant.classify_card(&PlayedStacks::new(), &[red(Two)], &red(Two)),
+            CardClass::Critical
+        );
+        // from the start card the stack can still go either way, a lost one or five
+        // only closes one of them
+        let started = PlayedStacks::from(vec![red(Start)]);
+        for face in [One, Five] {
+            assert_eq!(
+                variant.classify_card(&started, &[], &red(face)),
+                CardClass::Normal
+            );
+        }
+        let going_down = PlayedStacks::from(vec![red(Five)]);
+        assert_eq!(
+            variant.classify_card(&going_down, &[], &red(One)),
+            CardClass::Critical
+        );
+        assert_eq!(
+            variant.classify_card(&going_down, &[red(Two), red(Two)], &red(One)),
+            CardClass::Trash
+        );
+    }
+
+    #[test]
+    fn test_rainbow_hints() {
+        let rainbow = Card {
+            face: CardFace::Three,
+            suit: CardSuit::Rainbow,
+        };
+
+        assert!(Variant::Rainbow.hint_touches(&rainbow, &HintAction::SameSuit(CardSuit::Red)));
+        assert!(Variant::Rainbow.hint_touches(&rainbow, &HintAction::SameSuit(CardSuit::Blue)));
+        assert!(!Variant::Rainbow.is_hint_allowed(&HintAction::SameSuit(CardSuit::Rainbow)));
+        assert!(Variant::Rainbow.is_hint_allowed(&HintAction::SameSuit(CardSuit::Red)));
+        assert!(!Variant::Standard.is_hint_allowed(&HintAction::SameSuit(CardSuit::Rainbow)));
+
+        assert_eq!(
+            Variant::Rainbow.possible_suits(&vec![Hint::IsSuit(CardSuit::Red)].into()),
+            vec![CardSuit::Red, CardSuit::Rainbow]
+        );
+        assert_eq!(
+            Variant::Rainbow.possible_suits(
+                &vec![Hint::IsSuit(CardSuit::Red), Hint::IsSuit(CardSuit::Green)].into()
+            ),
+            vec![CardSuit::Rainbow]
+        );
+        assert_eq!(
+            Variant::Rainbow.possible_suits(
+                &vec![
+                    Hint::IsNotSuit(CardSuit::Red),
+                    Hint::IsNotSuit(CardSuit::Green),
+                    Hint::IsNotSuit(CardSuit::Yellow),
+                    Hint::IsNotSuit(CardSuit::White),
+                ]
+                .into()
+            ),
+            vec![CardSuit::Blue]
+        );
+    }
+
+    #[test]
+    fn test_standard_hint_touches() {
+        let card = Card {
+            face: CardFace::Two,
+            suit: CardSuit::Green,
+        };
+
+        assert!(Variant::Standard.hint_touches(&card, &HintAction::SameSuit(CardSuit::Green)));
+        assert!(Variant::Standard.hint_touches(&card, &HintAction::SameFace(CardFace::Two)));
+        assert!(!Variant::Standard.hint_touches(&card, &HintAction::SameSuit(CardSuit::Red)));
+        assert!(!Variant::Standard.hint_touches(&card, &HintAction::SameFace(CardFace::One)));
+    }
+}
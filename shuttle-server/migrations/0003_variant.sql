@@ -0,0 +1,2 @@
+-- Rule variant the game is played with, older games were all standard games
+ALTER TABLE game_config ADD COLUMN variant JSONB NOT NULL DEFAULT '"Standard"'::jsonb;
@@ -0,0 +1,3 @@
+-- Clue economy settings, older games all used the standard rules
+ALTER TABLE game_config ADD COLUMN clue_economy JSONB NOT NULL
+    DEFAULT '{"max_hints": 8, "half_hint_on_discard": false, "hint_on_completed_set": true}'::jsonb;
@@ -0,0 +1,2 @@
+-- How strictly actions are validated, older games were played with lenient rules
+ALTER TABLE game_config ADD COLUMN rules JSONB NOT NULL DEFAULT '"Lenient"'::jsonb;
@@ -0,0 +1,2 @@
+-- Optionally end games once a perfect score can't be reached anymore
+ALTER TABLE game_config ADD COLUMN end_when_perfect_lost BOOLEAN NOT NULL DEFAULT false;
@@ -211,13 +211,20 @@ async fn client_msg(client_id: ClientId, msg: Message, state: &ServerState) {
                     .await;
 
                 match result {
-                    Err(LobbyError::InvalidState(err) | LobbyError::InvalidPlayerAction(err)) => {
+                    Err(LobbyError::InvalidState(err)) => {
                         println!("error handling message: {:?}", err);
                         client
                             .sender
                             .send(ServerToClientMessage::Error(err))
                             .unwrap();
                     }
+                    Err(LobbyError::GameError(err)) => {
+                        println!("game error handling message: {:?}", err);
+                        client
+                            .sender
+                            .send(ServerToClientMessage::GameError(err))
+                            .unwrap();
+                    }
                     Err(LobbyError::SqlError(err)) => println!("sql error: {:?}", err),
                     _ => {}
                 }
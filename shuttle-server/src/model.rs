@@ -1,7 +1,7 @@
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 
 use serde::{Deserialize, Serialize};
-use shared::model::{GameConfig, PlayerAction, PlayerIndex};
+use shared::model::{ClueEconomy, GameConfig, PlayerAction, PlayerIndex, RuleValidation, Variant};
 use sqlx::{FromRow, PgPool};
 
 #[derive(Deserialize, Serialize)]
@@ -13,6 +13,10 @@ struct NewGameConfig {
     num_hints: i16,
     starting_player: i16,
     seed: i64,
+    variant: sqlx::types::Json<Variant>,
+    clue_economy: sqlx::types::Json<ClueEconomy>,
+    rules: sqlx::types::Json<RuleValidation>,
+    end_when_perfect_lost: bool,
 }
 
 #[derive(Serialize, Deserialize, FromRow)]
@@ -94,6 +98,10 @@ struct GameConfigRow {
     num_hints: i16,
     starting_player: i16,
     seed: i64,
+    variant: sqlx::types::Json<Variant>,
+    clue_economy: sqlx::types::Json<ClueEconomy>,
+    rules: sqlx::types::Json<RuleValidation>,
+    end_when_perfect_lost: bool,
 }
 
 #[derive(Serialize, FromRow)]
@@ -125,6 +133,10 @@ pub async fn get_game_config(pool: &PgPool, game_id: String) -> Result<GameConfi
         num_hints: game_config.num_hints as u8,
         starting_player: PlayerIndex(game_config.starting_player as usize),
         seed: game_config.seed as u64,
+        variant: game_config.variant.0,
+        clue_economy: game_config.clue_economy.0,
+        rules: game_config.rules.0,
+        end_when_perfect_lost: game_config.end_when_perfect_lost,
     })
 }
 
@@ -179,9 +191,13 @@ pub async fn create_game(
         num_hints: game_config.num_hints as i16,
         starting_player: game_config.starting_player.0 as i16,
         seed: game_config.seed as i64,
+        variant: sqlx::types::Json(game_config.variant),
+        clue_economy: sqlx::types::Json(game_config.clue_economy),
+        rules: sqlx::types::Json(game_config.rules),
+        end_when_perfect_lost: game_config.end_when_perfect_lost,
     };
 
-    let game_id = match sqlx::query_as::<_, GameConfigEntry>("INSERT INTO game_config (game_id, num_players, hand_size, num_fuses, num_hints, starting_player, seed) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING game_id")
+    let game_id = match sqlx::query_as::<_, GameConfigEntry>("INSERT INTO game_config (game_id, num_players, hand_size, num_fuses, num_hints, starting_player, seed, variant, clue_economy, rules, end_when_perfect_lost) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) RETURNING game_id")
         .bind(&new_game_config.game_id)
         .bind(&new_game_config.num_players)
         .bind(&new_game_config.hand_size)
@@ -189,6 +205,10 @@ pub async fn create_game(
         .bind(&new_game_config.num_hints)
         .bind(&new_game_config.starting_player)
         .bind(&new_game_config.seed)
+        .bind(new_game_config.variant)
+        .bind(new_game_config.clue_economy)
+        .bind(new_game_config.rules)
+        .bind(new_game_config.end_when_perfect_lost)
         .fetch_one(pool)
         .await
     {
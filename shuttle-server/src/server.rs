@@ -4,7 +4,11 @@ use std::collections::HashMap;
 use rand::rngs::StdRng;
 use shared::client_logic::*;
 use shared::model::GameConfig;
+use shared::model::GameError;
+use shared::model::GameStatus;
+use shared::model::PlayerAction;
 use shared::model::PlayerIndex;
+use shared::model::{ClueEconomy, RuleValidation, Variant};
 use sqlx::PgPool;
 use std::hash::{Hash, Hasher};
 use tokio::sync::mpsc;
@@ -41,15 +45,18 @@ struct GameLobby {
     players: Vec<SocketPlayer>,
     status: GameLobbyStatus,
     log: Vec<String>,
+    /// Variant the game is played with once it starts
+    variant: Variant,
 }
 
 impl GameLobby {
-    fn new(session: SessionId, players: Vec<SocketPlayer>) -> Self {
+    fn new(session: SessionId, players: Vec<SocketPlayer>, variant: Variant) -> Self {
         GameLobby {
             session_id: session,
             players: players,
             status: GameLobbyStatus::Waiting,
             log: vec![],
+            variant,
         }
     }
 
@@ -179,7 +186,8 @@ pub struct LobbyServer {
 #[derive(Debug)]
 pub enum LobbyError {
     InvalidState(String),
-    InvalidPlayerAction(String),
+    /// Refused by the game engine, or a stored game that can't be replayed
+    GameError(GameError),
     SqlError(sqlx::Error),
 }
 
@@ -189,6 +197,12 @@ impl From<sqlx::Error> for LobbyError {
     }
 }
 
+impl From<GameError> for LobbyError {
+    fn from(e: GameError) -> Self {
+        LobbyError::GameError(e)
+    }
+}
+
 // pub enum Result {
 //     GameCreated(GameConfig, Vec<String>),
 //     PlayedAction(PlayerAction, PlayerIndex, TurnCount),
@@ -209,15 +223,17 @@ impl LobbyServer {
 
         let players = get_players(&self.pool, game_id.clone()).await?;
 
-        let mut game_log = GameLog::new::<StdRng>(game_config.clone());
+        let mut game_log = GameLog::new::<StdRng>(game_config.clone())?;
 
         for action in game_actions {
-            game_log
-                .log(
-                    PlayerIndex(action.player_index as usize),
-                    action.player_action.0,
-                )
-                .map_err(|e| LobbyError::InvalidState(e))?;
+            // Older rows stored the current player rather than the player who moved a card,
+            // a card can only be moved by the owner of the hand anyway
+            let actor = match action.player_action.0 {
+                PlayerAction::MoveSlot(hand_owner, _, _) => hand_owner,
+                _ => PlayerIndex(action.player_index as usize),
+            };
+
+            game_log.log(actor, action.player_action.0)?;
         }
 
         let current_state = game_log.current_game_state();
@@ -231,11 +247,12 @@ impl LobbyServer {
                     connection: ConnectionState::Disconnected,
                 })
                 .collect(),
-            status: match current_state.outcome {
-                Some(_) => GameLobbyStatus::Ended(game_log),
-                None => GameLobbyStatus::Playing(game_log),
+            status: match current_state.status {
+                GameStatus::Finished(_) => GameLobbyStatus::Ended(game_log),
+                _ => GameLobbyStatus::Playing(game_log),
             },
             log: vec![],
+            variant: game_config.variant,
         };
 
         self.game_lobbies
@@ -302,7 +319,10 @@ impl LobbyServer {
         message: ClientToServerMessage,
     ) -> Result<(), LobbyError> {
         match message {
-            ClientToServerMessage::CreateGame { player_name } => {
+            ClientToServerMessage::CreateGame {
+                player_name,
+                variant,
+            } => {
                 let session_id = generate_unique_game_id(&self.pool).await?;
 
                 let game_lobby = self
@@ -314,6 +334,7 @@ impl LobbyServer {
                             name: player_name.clone(),
                             connection: ConnectionState::Connected(client.clone()),
                         }],
+                        variant,
                     ));
                 let _ = client.sender.send(ServerToClientMessage::CreatedGame {
                     session_id: session_id.clone(),
@@ -344,7 +365,11 @@ impl LobbyServer {
                 let game_lobby = self
                     .game_lobbies
                     .entry(SessionId(session_id.clone()))
-                    .or_insert(GameLobby::new(SessionId(session_id.clone()), vec![]));
+                    .or_insert(GameLobby::new(
+                        SessionId(session_id.clone()),
+                        vec![],
+                        Variant::default(),
+                    ));
 
                 let existing_player = game_lobby
                     .players
@@ -376,6 +401,7 @@ impl LobbyServer {
                 let session_id = self.get_lobby_session_for_client(client.client_id);
 
                 if let Some(session_id) = session_id {
+                    let mut new_game = Ok(());
                     let game_lobby =
                         self.game_lobbies
                             .entry(session_id.clone())
@@ -392,11 +418,16 @@ impl LobbyServer {
                                     num_hints: 8,
                                     starting_player: PlayerIndex(0),
                                     seed: rand::random::<u64>(),
+                                    variant: game_lobby.variant,
+                                    clue_economy: ClueEconomy::default(),
+                                    rules: RuleValidation::Lenient,
+                                    end_when_perfect_lost: false,
                                 };
-                                game_lobby.status = GameLobbyStatus::Playing(
-                                    GameLog::new::<StdRng>(config.clone()),
-                                );
+                                new_game = GameLog::new::<StdRng>(config.clone()).map(|game_log| {
+                                    game_lobby.status = GameLobbyStatus::Playing(game_log)
+                                });
                             });
+                    new_game?;
 
                     match game_lobby {
                         Entry::Occupied(game_lobby_entry) => {
@@ -484,7 +515,6 @@ impl LobbyServer {
                         let SessionId(session_id) = game_lobby.session_id.clone();
                         let current_game_state = game_log.current_game_state();
                         let turn_index = current_game_state.turn;
-                        let PlayerIndex(player_index) = current_game_state.current_player_index();
 
                         // TODO need to change this once player order is randomized
                         let existing_player = game_lobby
@@ -498,17 +528,14 @@ impl LobbyServer {
                             })
                             .ok_or(LobbyError::InvalidState("Player not found".to_string()))?;
 
-                        let result = game_log
-                            .log(PlayerIndex(existing_player), action)
-                            .map_err(|e| LobbyError::InvalidPlayerAction(e))?
-                            .clone();
+                        game_log.log(PlayerIndex(existing_player), action)?;
 
-                        if let Some(_) = result.post_event_game_state.outcome {
+                        if game_log.current_game_state().status.is_finished() {
                             game_lobby.status = GameLobbyStatus::Ended(game_log.clone());
                         }
                         game_lobby.update_players();
 
-                        save_action(&self.pool, &session_id, turn_index, action, player_index)
+                        save_action(&self.pool, &session_id, turn_index, action, existing_player)
                             .await
                             .map_err(|e| LobbyError::InvalidState(e.to_string()))?;
                     }
@@ -521,6 +548,8 @@ impl LobbyServer {
 
 #[cfg(test)]
 mod tests {
+    use shared::model::{CardFace, HintAction, SlotIndex};
+
     use super::*;
 
     #[test]
@@ -532,11 +561,15 @@ mod tests {
             num_hints: 8,
             starting_player: PlayerIndex(0),
             seed: 0,
+            variant: Variant::Standard,
+            clue_economy: ClueEconomy::default(),
+            rules: RuleValidation::Lenient,
+            end_when_perfect_lost: false,
         };
 
-        let deck = GameLog::new::<StdRng>(config.clone());
+        let deck = GameLog::new::<StdRng>(config.clone()).unwrap();
 
-        let deck_same_seed = GameLog::new::<StdRng>(config.clone());
+        let deck_same_seed = GameLog::new::<StdRng>(config.clone()).unwrap();
 
         assert_eq!(
             deck.current_game_state().draw_pile,
@@ -553,9 +586,13 @@ mod tests {
             num_hints: 8,
             starting_player: PlayerIndex(0),
             seed: 0,
+            variant: Variant::Standard,
+            clue_economy: ClueEconomy::default(),
+            rules: RuleValidation::Lenient,
+            end_when_perfect_lost: false,
         };
 
-        let deck = GameLog::new::<StdRng>(config.clone());
+        let deck = GameLog::new::<StdRng>(config.clone()).unwrap();
 
         let config = GameConfig {
             num_players: 2,
@@ -564,13 +601,106 @@ mod tests {
             num_hints: 8,
             starting_player: PlayerIndex(0),
             seed: 1,
+            variant: Variant::Standard,
+            clue_economy: ClueEconomy::default(),
+            rules: RuleValidation::Lenient,
+            end_when_perfect_lost: false,
         };
 
-        let deck_same_seed = GameLog::new::<StdRng>(config.clone());
+        let deck_same_seed = GameLog::new::<StdRng>(config.clone()).unwrap();
 
         assert_ne!(
             deck.current_game_state().draw_pile,
             deck_same_seed.current_game_state().draw_pile
         );
     }
+
+    fn playing_lobby_server() -> (LobbyServer, Vec<LobbyClient>) {
+        // Never connects, actions rejected by the engine don't reach the database
+        let pool = PgPool::connect_lazy("postgres://localhost/hanabi").unwrap();
+        let mut lobby_server = LobbyServer::new(pool);
+
+        let clients: Vec<_> = (0..2)
+            .map(|index| LobbyClient {
+                client_id: ClientId(index),
+                sender: mpsc::unbounded_channel().0,
+            })
+            .collect();
+
+        let session_id = SessionId("test-game".to_string());
+        lobby_server.game_lobbies.insert(
+            session_id.clone(),
+            GameLobby {
+                session_id,
+                players: clients
+                    .iter()
+                    .enumerate()
+                    .map(|(index, client)| SocketPlayer {
+                        name: format!("p{}", index),
+                        connection: ConnectionState::Connected(client.clone()),
+                    })
+                    .collect(),
+                status: GameLobbyStatus::Playing(
+                    GameLog::new::<StdRng>(GameConfig::new(2, 0)).unwrap(),
+                ),
+                log: vec![],
+                variant: Variant::Standard,
+            },
+        );
+
+        (lobby_server, clients)
+    }
+
+    fn logged_actions(lobby_server: &LobbyServer) -> usize {
+        match &lobby_server.game_lobbies.values().next().unwrap().status {
+            GameLobbyStatus::Playing(game_log) | GameLobbyStatus::Ended(game_log) => {
+                game_log.log.len()
+            }
+            GameLobbyStatus::Waiting => 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_non_current_player_cannot_act() {
+        let (mut lobby_server, clients) = playing_lobby_server();
+
+        for action in [
+            PlayerAction::PlayCard(SlotIndex(0)),
+            PlayerAction::DiscardCard(SlotIndex(0)),
+            PlayerAction::GiveHint(PlayerIndex(0), HintAction::SameFace(CardFace::One)),
+        ] {
+            let result = lobby_server
+                .message_received(&clients[1], ClientToServerMessage::PlayerAction { action })
+                .await;
+
+            assert!(matches!(
+                result,
+                Err(LobbyError::GameError(GameError::NotYourTurn {
+                    current_player: PlayerIndex(0)
+                }))
+            ));
+        }
+
+        assert_eq!(logged_actions(&lobby_server), 0);
+    }
+
+    #[tokio::test]
+    async fn test_player_cannot_move_cards_in_another_hand() {
+        let (mut lobby_server, clients) = playing_lobby_server();
+
+        let result = lobby_server
+            .message_received(
+                &clients[1],
+                ClientToServerMessage::PlayerAction {
+                    action: PlayerAction::MoveSlot(PlayerIndex(0), SlotIndex(0), SlotIndex(1)),
+                },
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(LobbyError::GameError(GameError::NotYourHand))
+        ));
+        assert_eq!(logged_actions(&lobby_server), 0);
+    }
 }
@@ -20,6 +20,7 @@ use shared::client_logic::*;
 use shared::model::PlayerAction;
 use shared::model::PlayerIndex;
 use shared::model::SlotIndex;
+use shared::model::Variant;
 use wasm_bindgen::prelude::*;
 use web_sys::{ErrorEvent, MessageEvent, WebSocket};
 mod input;
@@ -73,6 +74,7 @@ pub enum TuiState {
     CreatingGame {
         player_name: String,
         server_address: String,
+        variant: Variant,
     },
     HanabiApp {
         hanabi_app: HanabiApp,
@@ -339,6 +341,7 @@ impl eframe::App for HelloApp {
                             self.tui_state = TuiState::CreatingGame {
                                 player_name: player_name.clone(),
                                 server_address: app_input.server_address.clone(),
+                                variant: app_input.variant,
                             };
                         }
                     }
@@ -349,12 +352,14 @@ impl eframe::App for HelloApp {
             TuiState::CreatingGame {
                 ref player_name,
                 ref server_address,
+                variant,
             } => {
                 if let None = self.websocket {
                     let result = setup_websocket(
                         server_address.clone(),
                         player_name.clone(),
                         None,
+                        variant,
                         self.server_to_client_sender.clone(),
                         ctx.clone(),
                     );
@@ -577,6 +582,7 @@ impl eframe::App for HelloApp {
                         server_address.clone(),
                         player_name.clone(),
                         Some(session_id.clone()),
+                        Variant::default(),
                         self.server_to_client_sender.clone(),
                         ctx.clone(),
                     );
@@ -598,6 +604,9 @@ impl eframe::App for HelloApp {
                         ServerToClientMessage::Error(error) => {
                             console_log!("Got Error... {:?}", error);
                         }
+                        ServerToClientMessage::GameError(error) => {
+                            console_log!("Got Game Error... {}", error);
+                        }
                     },
                     _ => {}
                 };
@@ -659,6 +668,7 @@ fn setup_websocket(
     url: String,
     player_name: String,
     session_id: Option<String>,
+    variant: Variant,
     server_to_client_sender: Sender<ServerToClientMessage>,
     ctx: egui::Context,
 ) -> Result<WebSocket, JsValue> {
@@ -721,6 +731,7 @@ fn setup_websocket(
     let init_message = serde_json::to_string(&match session_id {
         None => ClientToServerMessage::CreateGame {
             player_name: player_name.clone(),
+            variant,
         },
         Some(session_id) => ClientToServerMessage::Join {
             player_name: player_name.clone(),